@@ -50,6 +50,7 @@ struct FormatMember {
     compatibility: Ident,
     components: [u8; 4],
     compression: Option<Ident>,
+    packed: bool,
     planes: Vec<Ident>,
     texels_per_block: u8,
     type_color: Option<Ident>,
@@ -66,6 +67,9 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
     let enum_items = members.iter().map(|FormatMember { name, ffi_name, .. }| {
         quote! { #name = ash::vk::Format::#ffi_name.as_raw(), }
     });
+    let all_items = members.iter().map(|FormatMember { name, .. }| {
+        quote! { Format::#name, }
+    });
     let aspects_items = members.iter().map(
         |FormatMember {
              name,
@@ -149,6 +153,10 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
                 .map(|x| quote! { Self::#name => Some(CompressionType::#x), })
         },
     );
+    let is_packed_items = members
+        .iter()
+        .filter(|FormatMember { packed, .. }| *packed)
+        .map(|FormatMember { name, .. }| quote! { Self::#name => true, });
     let planes_items = members
         .iter()
         .filter_map(|FormatMember { name, planes, .. }| {
@@ -362,6 +370,11 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
             #(#enum_items)*
         }
 
+        /// All formats, in the order in which they are declared.
+        const ALL_FORMATS: &[Format] = &[
+            #(#all_items)*
+        ];
+
         impl Format {
             /// Returns the aspects that images of this format have.
             pub fn aspects(self) -> ImageAspects {
@@ -397,7 +410,7 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
             /// not have a well-defined size. Multi-planar formats store the color components
             /// disjointly in memory, and therefore do not have a well-defined size for all
             /// components as a whole. The individual planes do have a well-defined size.
-            pub fn block_size(self) -> Option<DeviceSize> {
+            pub const fn block_size(self) -> Option<DeviceSize> {
                 match self {
                     #(#block_size_items)*
                     _ => None,
@@ -422,7 +435,7 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
             /// For block-compressed formats, the number of bits in individual components is not
             /// well-defined, and the return value is merely binary: 1 indicates a component
             /// that is present in the format, 0 indicates one that is absent.
-            pub fn components(self) -> [u8; 4] {
+            pub const fn components(self) -> [u8; 4] {
                 match self {
                     #(#components_items)*
                 }
@@ -430,18 +443,27 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
 
             /// Returns the block compression scheme used for this format, if any. Returns `None` if
             /// the format does not use compression.
-            pub fn compression(self) -> Option<CompressionType> {
+            pub const fn compression(self) -> Option<CompressionType> {
                 match self {
                     #(#compression_items)*
                     _ => None,
                 }
             }
 
+            /// Returns whether the components of a texel block are packed together into one or more
+            /// larger elements, such as the 32-bit element of `A2B10G10R10_UNORM_PACK32`.
+            pub const fn is_packed(self) -> bool {
+                match self {
+                    #(#is_packed_items)*
+                    _ => false,
+                }
+            }
+
             /// For multi-planar formats, returns a slice of length 2 or 3, containing the
             /// equivalent regular format of each plane.
             ///
             /// For non-planar formats, returns the empty slice.
-            pub fn planes(self) -> &'static [Self] {
+            pub const fn planes(self) -> &'static [Self] {
                 match self {
                     #(#planes_items)*
                     _ => &[],
@@ -459,7 +481,7 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
 
             /// Returns the numeric data type of the color aspect of this format. Returns `None`
             /// for depth/stencil formats.
-            pub fn type_color(self) -> Option<NumericType> {
+            pub const fn type_color(self) -> Option<NumericType> {
                 match self {
                     #(#type_color_items)*
                     _ => None,
@@ -490,7 +512,7 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
             /// If an image view is created for one of the formats for which this function returns
             /// `Some`, with the `color` aspect selected, then the view and any samplers that sample
             /// it must be created with an attached sampler YCbCr conversion object.
-            pub fn ycbcr_chroma_sampling(self) -> Option<ChromaSampling> {
+            pub const fn ycbcr_chroma_sampling(self) -> Option<ChromaSampling> {
                 match self {
                     #(#ycbcr_chroma_sampling_items)*
                     _ => None,
@@ -682,6 +704,7 @@ fn formats_members(
                     .compressed
                     .as_ref()
                     .map(|c| format_ident!("{}", c.replace(' ', "_"))),
+                packed: format.packed.is_some(),
                 planes: vec![],
                 texels_per_block: format.texelsPerBlock,
                 type_color: None,
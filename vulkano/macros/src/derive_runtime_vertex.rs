@@ -0,0 +1,117 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::derive_vertex::NameMeta;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use proc_macro_crate::{crate_name, FoundCrate};
+use quote::quote;
+use syn::{parse::Parse, Data, DataStruct, Error, Fields, Ident, LitStr, Result};
+
+pub fn derive_runtime_vertex(ast: syn::DeriveInput) -> Result<TokenStream> {
+    let struct_name = &ast.ident;
+
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(Error::new_spanned(
+                ast,
+                "Expected a struct with named fields",
+            ));
+        }
+    };
+
+    let found_crate = crate_name("vulkano").expect("vulkano is present in `Cargo.toml`");
+
+    let crate_ident = match found_crate {
+        // We use `vulkano` by default as we are exporting crate as vulkano in vulkano/lib.rs.
+        FoundCrate::Itself => Ident::new("vulkano", Span::call_site()),
+        FoundCrate::Name(name) => Ident::new(&name, Span::call_site()),
+    };
+
+    let mut attributes = quote! {};
+
+    for field in fields.iter() {
+        let field_name = field.ident.to_owned().unwrap();
+        let field_ty = &field.ty;
+        let mut name = LitStr::new(&field_name.to_string(), Span::call_site());
+        let mut format = None;
+        for attr in &field.attrs {
+            let attr_ident = if let Some(ident) = attr.path.get_ident() {
+                ident
+            } else {
+                continue;
+            };
+            if attr_ident == "name" {
+                let meta = attr.parse_args_with(NameMeta::parse)?;
+                let mut names = meta.lit_str_list.into_iter();
+                name = match (names.next(), names.next()) {
+                    (Some(name), None) => name,
+                    _ => {
+                        return Err(Error::new_spanned(
+                            attr,
+                            "Expected exactly one name for a `RuntimeVertex` field",
+                        ))
+                    }
+                };
+            } else if attr_ident == "format" {
+                format = Some(attr.parse_args_with(Ident::parse)?);
+            }
+        }
+        let format = match format {
+            Some(format) => format,
+            None => {
+                return Err(Error::new(
+                    field_name.span(),
+                    "Expected `#[format(...)]`-attribute with valid `vulkano::format::Format`",
+                ))
+            }
+        };
+
+        attributes = quote! {
+            #attributes
+
+            let field_ptr = unsafe { core::ptr::addr_of!((*base).#field_name) };
+            let field_size = std::mem::size_of::<#field_ty>();
+            let format = Format::#format;
+            let format_size = format.block_size().expect("no block size for format") as usize;
+            let num_elements = field_size / format_size;
+            let remainder = field_size % format_size;
+            assert!(remainder == 0, "struct field `{}` size does not fit multiple of format size", #name);
+            attributes.push(VertexAttribute {
+                name: std::borrow::Cow::Borrowed(#name),
+                format,
+                num_elements: Some(num_elements as u32),
+                offset: Some(field_ptr as usize - base as usize),
+            });
+        };
+    }
+
+    Ok(TokenStream::from(quote! {
+        #[allow(unsafe_code)]
+        impl #crate_ident::pipeline::graphics::vertex_input::RuntimeVertex for #struct_name {
+            fn attributes() -> Vec<#crate_ident::pipeline::graphics::vertex_input::VertexAttribute> {
+                #[allow(unused_imports)]
+                use #crate_ident::format::Format;
+                use #crate_ident::pipeline::graphics::vertex_input::VertexAttribute;
+
+                let uninit = core::mem::MaybeUninit::<Self>::uninit();
+                let base = uninit.as_ptr();
+                let mut attributes = Vec::new();
+
+                #attributes
+
+                attributes
+            }
+        }
+    }))
+}
@@ -112,8 +112,8 @@ pub fn derive_vertex(ast: syn::DeriveInput) -> Result<TokenStream> {
     }))
 }
 
-struct NameMeta {
-    lit_str_list: Punctuated<LitStr, Token![,]>,
+pub(crate) struct NameMeta {
+    pub(crate) lit_str_list: Punctuated<LitStr, Token![,]>,
 }
 
 impl Parse for NameMeta {
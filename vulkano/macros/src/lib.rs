@@ -10,6 +10,7 @@
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
+mod derive_runtime_vertex;
 mod derive_vertex;
 
 #[proc_macro_derive(Vertex, attributes(name, format))]
@@ -17,3 +18,10 @@ pub fn proc_derive_vertex(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     derive_vertex::derive_vertex(ast).unwrap_or_else(|err| err.to_compile_error().into())
 }
+
+#[proc_macro_derive(RuntimeVertex, attributes(name, format))]
+pub fn proc_derive_runtime_vertex(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    derive_runtime_vertex::derive_runtime_vertex(ast)
+        .unwrap_or_else(|err| err.to_compile_error().into())
+}
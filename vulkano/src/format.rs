@@ -162,6 +162,243 @@ impl Format {
                 | Format::R8_UINT
         )
     }
+
+    /// Returns the format of a vertex attribute that is read by a GLSL shader input of the given
+    /// scalar or vector type, such as `"vec3"` or `"uint"`.
+    ///
+    /// The supported types map to formats with 32-bit components:
+    ///
+    /// - `float`, `vec2`, `vec3` and `vec4` map to `R32_SFLOAT` through `R32G32B32A32_SFLOAT`.
+    /// - `int`, `ivec2`, `ivec3` and `ivec4` map to `R32_SINT` through `R32G32B32A32_SINT`.
+    /// - `uint`, `uvec2`, `uvec3` and `uvec4` map to `R32_UINT` through `R32G32B32A32_UINT`.
+    ///
+    /// Returns `None` for any other type, including `double` and `bool` types, matrices and
+    /// arrays. The name must match exactly, without surrounding whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vulkano::format::Format;
+    ///
+    /// assert_eq!(Format::from_glsl_scalar_vec("vec3"), Some(Format::R32G32B32_SFLOAT));
+    /// assert_eq!(Format::from_glsl_scalar_vec("uvec2"), Some(Format::R32G32_UINT));
+    /// assert_eq!(Format::from_glsl_scalar_vec("mat4"), None);
+    /// ```
+    pub fn from_glsl_scalar_vec(ty: &str) -> Option<Format> {
+        let format = match ty {
+            "float" => Format::R32_SFLOAT,
+            "vec2" => Format::R32G32_SFLOAT,
+            "vec3" => Format::R32G32B32_SFLOAT,
+            "vec4" => Format::R32G32B32A32_SFLOAT,
+            "int" => Format::R32_SINT,
+            "ivec2" => Format::R32G32_SINT,
+            "ivec3" => Format::R32G32B32_SINT,
+            "ivec4" => Format::R32G32B32A32_SINT,
+            "uint" => Format::R32_UINT,
+            "uvec2" => Format::R32G32_UINT,
+            "uvec3" => Format::R32G32B32_UINT,
+            "uvec4" => Format::R32G32B32A32_UINT,
+            _ => return None,
+        };
+
+        Some(format)
+    }
+
+    /// Returns the number of components that are present in the format, which is the number of
+    /// nonzero elements of [`components`](Self::components).
+    ///
+    /// For example, `R32G32B32_SFLOAT` and `B10G11R11_UFLOAT_PACK32` both have 3 components. For
+    /// depth/stencil formats, the depth and stencil components are counted.
+    #[inline]
+    pub fn component_count(self) -> u8 {
+        self.components().iter().filter(|&&bits| bits > 0).count() as u8
+    }
+
+    /// Returns the numeric type of the components of the format, if it can represent an
+    /// uncompressed color value.
+    ///
+    /// This is the same as [`type_color`](Self::type_color), except that `None` is also returned
+    /// for block-compressed and YCbCr formats, which can't be used as vertex attributes.
+    #[inline]
+    pub const fn numeric_type(self) -> Option<NumericType> {
+        if self.compression().is_some() || self.ycbcr_chroma_sampling().is_some() {
+            return None;
+        }
+
+        self.type_color()
+    }
+
+    /// Returns whether the numeric type of the color components of the format is
+    /// [`NumericType::SRGB`]. This includes block-compressed sRGB formats.
+    #[inline]
+    pub fn is_srgb(self) -> bool {
+        self.type_color() == Some(NumericType::SRGB)
+    }
+
+    /// Returns whether the format can, in principle, be used as the format of a vertex attribute.
+    ///
+    /// This is true for every single-plane format that has a [`numeric_type`](Self::numeric_type),
+    /// which excludes depth/stencil, block-compressed, YCbCr and multi-planar formats. The Vulkan
+    /// specification only allows formats of this kind to have the `VERTEX_BUFFER` buffer format
+    /// feature. Whether a format is actually supported for vertex buffers depends on the device,
+    /// and can be checked in the `buffer_features` of
+    /// [`PhysicalDevice::format_properties`](crate::device::physical::PhysicalDevice::format_properties).
+    #[inline]
+    pub const fn is_vertex_attribute_format(self) -> bool {
+        self.numeric_type().is_some() && self.planes().is_empty() && self.block_size().is_some()
+    }
+
+    /// Returns all formats for which
+    /// [`is_vertex_attribute_format`](Self::is_vertex_attribute_format) returns `true`, in the
+    /// order in which they are declared.
+    #[inline]
+    pub const fn vertex_attribute_formats() -> &'static [Format] {
+        &VERTEX_ATTRIBUTE_FORMATS
+    }
+
+    /// Returns the alignment in bytes that the data of the format needs when it is fetched as a
+    /// vertex attribute.
+    ///
+    /// Vulkan requires the offset of a vertex attribute, and the stride of its binding, to be a
+    /// multiple of the size of the largest component type of its format. For example, this is 4
+    /// for `R32G32B32_SFLOAT` and 8 for `R64_SFLOAT`. Packed formats are read as a whole, so
+    /// their alignment is the size of an element, such as 4 for `A2B10G10R10_UNORM_PACK32`.
+    #[inline]
+    pub fn required_alignment(self) -> DeviceSize {
+        if self.is_packed() {
+            return self.block_size().unwrap_or(1);
+        }
+
+        let bits = self.components().into_iter().max().unwrap_or(0) as DeviceSize;
+
+        (bits / 8).max(1)
+    }
+
+    /// Returns the name of the Rust type that holds a single element of the format, for example
+    /// to generate the definition of a vertex struct.
+    ///
+    /// Formats with one component map to a primitive type, such as `u8` for `R8_UNORM`, and
+    /// formats with more components map to an array, such as `[f32; 3]` for `R32G32B32_SFLOAT`.
+    /// 16-bit floating point components map to `half::f16`. Packed formats map to the unsigned
+    /// integer that holds the whole element.
+    ///
+    /// Returns `None` if the format isn't a
+    /// [vertex attribute format](Self::is_vertex_attribute_format), such as depth/stencil,
+    /// block-compressed and YCbCr formats.
+    pub fn rust_type_name(self) -> Option<&'static str> {
+        if !self.is_vertex_attribute_format() {
+            return None;
+        }
+
+        if self.is_packed() {
+            return match self.block_size() {
+                Some(1) => Some("u8"),
+                Some(2) => Some("u16"),
+                Some(4) => Some("u32"),
+                _ => None,
+            };
+        }
+
+        let bits = self.components().into_iter().max().unwrap();
+        let names = match (self.numeric_type()?, bits) {
+            (NumericType::SFLOAT, 16) => [
+                "half::f16",
+                "[half::f16; 2]",
+                "[half::f16; 3]",
+                "[half::f16; 4]",
+            ],
+            (NumericType::SFLOAT, 32) => ["f32", "[f32; 2]", "[f32; 3]", "[f32; 4]"],
+            (NumericType::SFLOAT, 64) => ["f64", "[f64; 2]", "[f64; 3]", "[f64; 4]"],
+            (NumericType::SINT | NumericType::SNORM | NumericType::SSCALED, 8) => {
+                ["i8", "[i8; 2]", "[i8; 3]", "[i8; 4]"]
+            }
+            (NumericType::SINT | NumericType::SNORM | NumericType::SSCALED, 16) => {
+                ["i16", "[i16; 2]", "[i16; 3]", "[i16; 4]"]
+            }
+            (NumericType::SINT | NumericType::SNORM | NumericType::SSCALED, 32) => {
+                ["i32", "[i32; 2]", "[i32; 3]", "[i32; 4]"]
+            }
+            (NumericType::SINT | NumericType::SNORM | NumericType::SSCALED, 64) => {
+                ["i64", "[i64; 2]", "[i64; 3]", "[i64; 4]"]
+            }
+            (
+                NumericType::UINT | NumericType::UNORM | NumericType::USCALED | NumericType::SRGB,
+                8,
+            ) => ["u8", "[u8; 2]", "[u8; 3]", "[u8; 4]"],
+            (
+                NumericType::UINT | NumericType::UNORM | NumericType::USCALED | NumericType::SRGB,
+                16,
+            ) => ["u16", "[u16; 2]", "[u16; 3]", "[u16; 4]"],
+            (
+                NumericType::UINT | NumericType::UNORM | NumericType::USCALED | NumericType::SRGB,
+                32,
+            ) => ["u32", "[u32; 2]", "[u32; 3]", "[u32; 4]"],
+            (
+                NumericType::UINT | NumericType::UNORM | NumericType::USCALED | NumericType::SRGB,
+                64,
+            ) => ["u64", "[u64; 2]", "[u64; 3]", "[u64; 4]"],
+            _ => return None,
+        };
+
+        Some(names[self.component_count() as usize - 1])
+    }
+}
+
+// The formats returned by `Format::vertex_attribute_formats`. They are selected from all formats
+// with `Format::is_vertex_attribute_format` at compile time, so the two always agree.
+const VERTEX_ATTRIBUTE_FORMATS: [Format; NUM_VERTEX_ATTRIBUTE_FORMATS] = {
+    let mut formats = [Format::R8_UNORM; NUM_VERTEX_ATTRIBUTE_FORMATS];
+    let mut count = 0;
+    let mut index = 0;
+
+    while index < ALL_FORMATS.len() {
+        if ALL_FORMATS[index].is_vertex_attribute_format() {
+            formats[count] = ALL_FORMATS[index];
+            count += 1;
+        }
+
+        index += 1;
+    }
+
+    formats
+};
+
+const NUM_VERTEX_ATTRIBUTE_FORMATS: usize = {
+    let mut count = 0;
+    let mut index = 0;
+
+    while index < ALL_FORMATS.len() {
+        if ALL_FORMATS[index].is_vertex_attribute_format() {
+            count += 1;
+        }
+
+        index += 1;
+    }
+
+    count
+};
+
+// Formats are serialized as their Vulkan enum value.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Format {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(*self as i32)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Format {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = i32::deserialize(deserializer)?;
+
+        Format::try_from(ash::vk::Format::from_raw(value)).map_err(|_| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Signed(value as i64),
+                &"a Vulkan format value",
+            )
+        })
+    }
 }
 
 impl From<Format> for ash::vk::Format {
@@ -979,3 +1216,41 @@ impl From<ash::vk::FormatFeatureFlags> for FormatFeatures {
         Self::from(ash::vk::FormatFeatureFlags2::from_raw(val.as_raw() as u64))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Format;
+
+    #[test]
+    fn rust_type_name() {
+        assert_eq!(Format::R8_UNORM.rust_type_name(), Some("u8"));
+        assert_eq!(Format::R32G32B32_SFLOAT.rust_type_name(), Some("[f32; 3]"));
+        assert_eq!(Format::B8G8R8A8_SRGB.rust_type_name(), Some("[u8; 4]"));
+        assert_eq!(Format::R16G16_SNORM.rust_type_name(), Some("[i16; 2]"));
+        assert_eq!(
+            Format::R16G16B16A16_SFLOAT.rust_type_name(),
+            Some("[half::f16; 4]"),
+        );
+        assert_eq!(
+            Format::A2B10G10R10_UNORM_PACK32.rust_type_name(),
+            Some("u32")
+        );
+        assert_eq!(Format::D32_SFLOAT.rust_type_name(), None);
+        assert_eq!(Format::BC1_RGB_UNORM_BLOCK.rust_type_name(), None);
+
+        // Every vertex attribute format has a type.
+        for &format in Format::vertex_attribute_formats() {
+            assert!(format.rust_type_name().is_some(), "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn required_alignment() {
+        assert_eq!(Format::R8_UNORM.required_alignment(), 1);
+        assert_eq!(Format::R16G16_SFLOAT.required_alignment(), 2);
+        assert_eq!(Format::R32G32B32_SFLOAT.required_alignment(), 4);
+        assert_eq!(Format::R64_SFLOAT.required_alignment(), 8);
+        assert_eq!(Format::A2B10G10R10_UNORM_PACK32.required_alignment(), 4);
+        assert_eq!(Format::R5G6B5_UNORM_PACK16.required_alignment(), 2);
+    }
+}
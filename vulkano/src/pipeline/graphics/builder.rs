@@ -992,9 +992,11 @@ where
                 // Check that the vertex input state contains attributes for all the shader's input
                 // variables.
                 for element in entry_point.input_interface().elements() {
-                    assert!(!element.ty.is_64bit); // TODO: implement
-                    let location_range =
-                        element.location..element.location + element.ty.num_locations();
+                    // An attribute whose format takes up two locations is only specified at the
+                    // first of them.
+                    let location_range = (element.location
+                        ..element.location + element.ty.num_locations())
+                        .step_by(element.ty.locations_per_element() as usize);
 
                     for location in location_range {
                         let attribute_desc =
@@ -2670,6 +2672,11 @@ where
                     },
                 ));
 
+                // The descriptions are sorted so that the create info doesn't depend on the
+                // iteration order of the maps, which is unspecified.
+                vertex_binding_descriptions_vk.sort_unstable_by_key(|desc| desc.binding);
+                vertex_attribute_descriptions_vk.sort_unstable_by_key(|desc| desc.location);
+
                 let vertex_input_state =
                     vertex_input_state_vk.insert(ash::vk::PipelineVertexInputStateCreateInfo {
                         flags: ash::vk::PipelineVertexInputStateCreateFlags::empty(),
@@ -7,15 +7,13 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use super::VertexMemberInfo;
+use super::{definition::attributes_by_name, VertexMemberInfo};
 use crate::{
     pipeline::graphics::vertex_input::{
-        IncompatibleVertexDefinitionError, Vertex, VertexDefinition,
-        VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate,
-        VertexInputState,
+        IncompatibleVertexDefinitionError, Vertex, VertexDefinition, VertexInputBindingDescription,
+        VertexInputRate, VertexInputState,
     },
     shader::ShaderInterface,
-    DeviceSize,
 };
 use std::{
     fmt::{Debug, Error as FmtError, Formatter},
@@ -108,64 +106,29 @@ unsafe impl VertexDefinition for BuffersDefinition {
         &self,
         interface: &ShaderInterface,
     ) -> Result<VertexInputState, IncompatibleVertexDefinitionError> {
+        self.definition_all(interface)
+            .map_err(|mut errors| errors.remove(0))
+    }
+
+    fn definition_all(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<VertexInputState, Vec<IncompatibleVertexDefinitionError>> {
         let bindings = self
             .0
             .iter()
             .enumerate()
             .map(|(binding, &buffer)| (binding as u32, buffer.into()));
-        let mut attributes: Vec<(u32, VertexInputAttributeDescription)> = Vec::new();
-
-        for element in interface.elements() {
-            let name = element.name.as_ref().unwrap();
-
-            let (infos, binding) = self
-                .0
-                .iter()
-                .enumerate()
-                .find_map(|(binding, buffer)| {
+        let attributes = attributes_by_name(
+            interface,
+            |name| {
+                self.0.iter().enumerate().find_map(|(binding, buffer)| {
                     (buffer.info_fn)(name).map(|infos| (infos, binding as u32))
                 })
-                .ok_or_else(||
-                    // TODO: move this check to GraphicsPipelineBuilder
-                    IncompatibleVertexDefinitionError::MissingAttribute {
-                        attribute: name.clone().into_owned(),
-                    })?;
-
-            // TODO: ShaderInterfaceEntryType does not properly support 64bit.
-            //       Once it does the below logic around num_elements and num_locations
-            //       might have to be updated.
-            if infos.num_components() != element.ty.num_components
-                || infos.num_elements != element.ty.num_locations()
-            {
-                return Err(IncompatibleVertexDefinitionError::FormatMismatch {
-                    attribute: name.clone().into_owned(),
-                    shader: element.ty,
-                    definition: infos,
-                });
-            }
-
-            let mut offset = infos.offset as DeviceSize;
-            let block_size = infos.format.block_size().unwrap();
-            // Double precision formats can exceed a single location.
-            // R64B64G64A64_SFLOAT requires two locations, so we need to adapt how we bind
-            let location_range = if block_size > 16 {
-                (element.location..element.location + 2 * element.ty.num_locations()).step_by(2)
-            } else {
-                (element.location..element.location + element.ty.num_locations()).step_by(1)
-            };
-
-            for location in location_range {
-                attributes.push((
-                    location,
-                    VertexInputAttributeDescription {
-                        binding,
-                        format: infos.format,
-                        offset: offset as u32,
-                    },
-                ));
-                offset += block_size;
-            }
-        }
+            },
+            // `Vertex` only looks up members by name, so there are no names to suggest.
+            Vec::new,
+        )?;
 
         Ok(VertexInputState::new()
             .bindings(bindings)
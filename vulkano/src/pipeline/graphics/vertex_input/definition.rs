@@ -51,9 +51,16 @@
 //! ```
 
 use crate::{
-    pipeline::graphics::vertex_input::{VertexInputState, VertexMemberInfo},
-    shader::{ShaderInterface, ShaderInterfaceEntryType},
+    device::Properties,
+    format::Format,
+    pipeline::graphics::vertex_input::{
+        VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate,
+        VertexInputState, VertexMemberInfo,
+    },
+    shader::{ShaderInterface, ShaderInterfaceEntryType, ShaderScalarType},
+    DeviceSize,
 };
+use ahash::HashMap;
 use std::{
     error::Error,
     fmt::{Display, Error as FmtError, Formatter},
@@ -68,6 +75,311 @@ pub unsafe trait VertexDefinition {
         &self,
         interface: &ShaderInterface,
     ) -> Result<VertexInputState, IncompatibleVertexDefinitionError>;
+
+    /// Builds the vertex definition like [`definition`](Self::definition), but reports every
+    /// incompatibility with the shader interface instead of only the first one.
+    ///
+    /// The default implementation returns the single error of `definition`.
+    fn definition_all(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<VertexInputState, Vec<IncompatibleVertexDefinitionError>> {
+        self.definition(interface).map_err(|err| vec![err])
+    }
+
+    /// Builds the vertex definition like [`definition`](Self::definition), but with the given
+    /// matching mode. `MatchMode::Strict` is equivalent to calling `definition`.
+    ///
+    /// With `MatchMode::AllowMissingShaderInputs`, the missing inputs are taken from the errors
+    /// returned by [`definition_all`](Self::definition_all), so implementations that are meant to
+    /// be used with that mode should report every missing input there.
+    fn definition_with_mode(
+        &self,
+        interface: &ShaderInterface,
+        mode: MatchMode,
+    ) -> Result<VertexInputState, IncompatibleVertexDefinitionError> {
+        let mut missing = Vec::new();
+        let mut state = match mode {
+            MatchMode::Strict => return self.definition(interface),
+            MatchMode::IgnoreUnusedDefinitionMembers => self.definition(interface)?,
+            MatchMode::AllowMissingShaderInputs => match self.definition_all(interface) {
+                Ok(state) => state,
+                Err(errors) => {
+                    // Every missing input is reported at once, so the definition only has to be
+                    // created once more, without them.
+                    let elements = interface.elements();
+                    let mut is_missing = vec![false; elements.len()];
+
+                    for err in errors {
+                        let index = match &err {
+                            IncompatibleVertexDefinitionError::MissingAttribute {
+                                attribute,
+                                ..
+                            } => elements
+                                .iter()
+                                .position(|e| e.name.as_deref() == Some(attribute.as_str())),
+                            IncompatibleVertexDefinitionError::MissingLocation { location } => {
+                                elements.iter().position(|e| {
+                                    (e.location..e.location + e.ty.num_locations())
+                                        .contains(location)
+                                })
+                            }
+                            _ => None,
+                        };
+
+                        match index {
+                            Some(index) => is_missing[index] = true,
+                            None => return Err(err),
+                        }
+                    }
+
+                    let mut present = Vec::with_capacity(elements.len());
+
+                    for (element, is_missing) in elements.iter().zip(is_missing) {
+                        if is_missing {
+                            missing.push(element.clone());
+                        } else {
+                            present.push(element.clone());
+                        }
+                    }
+
+                    // Safety: `present` is a subset of the elements of an existing interface.
+                    let interface = unsafe { ShaderInterface::new_unchecked(present) };
+                    self.definition(&interface)?
+                }
+            },
+        };
+
+        let used_locations: Vec<u32> = interface
+            .elements()
+            .iter()
+            .flat_map(|e| e.location..e.location + e.ty.num_locations())
+            .collect();
+        state
+            .attributes
+            .retain(|location, _| used_locations.contains(location));
+        let attributes = &state.attributes;
+        state
+            .bindings
+            .retain(|binding, _| attributes.values().any(|a| a.binding == *binding));
+
+        if !missing.is_empty() {
+            let binding = state
+                .bindings
+                .keys()
+                .max()
+                .map_or(0, |&binding| binding + 1);
+            state.bindings.insert(
+                binding,
+                VertexInputBindingDescription {
+                    stride: 0,
+                    input_rate: VertexInputRate::Vertex,
+                },
+            );
+
+            for element in missing {
+                let format = default_input_format(&element.ty);
+
+                for location in (element.location..element.location + element.ty.num_locations())
+                    .step_by(element.ty.locations_per_element() as usize)
+                {
+                    state.attributes.insert(
+                        location,
+                        VertexInputAttributeDescription {
+                            binding,
+                            format,
+                            offset: 0,
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(state)
+    }
+
+    /// Builds the vertex definition like [`definition`](Self::definition), and also returns
+    /// diagnostics about attributes that are compatible with the shader, but are likely not to
+    /// behave as intended.
+    ///
+    /// The default implementation inspects the result of `definition`.
+    fn definition_with_diagnostics(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<
+        (VertexInputState, Vec<VertexDefinitionDiagnostic>),
+        IncompatibleVertexDefinitionError,
+    > {
+        let state = self.definition(interface)?;
+
+        let mut srgb_attributes: Vec<_> = state
+            .attributes
+            .iter()
+            .filter(|(_, attribute)| attribute.format.is_srgb())
+            .map(|(&location, attribute)| (location, attribute.format))
+            .collect();
+        srgb_attributes.sort_by_key(|&(location, _)| location);
+
+        let diagnostics = srgb_attributes
+            .into_iter()
+            .map(|(location, format)| {
+                let name = interface
+                    .entries()
+                    .filter(|element| {
+                        (element.location..element.location + element.ty.num_locations())
+                            .contains(&location)
+                    })
+                    .find_map(|element| element.name.as_deref());
+
+                VertexDefinitionDiagnostic::SrgbAttribute {
+                    attribute: match name {
+                        Some(name) if !name.is_empty() => name.to_owned(),
+                        _ => format!("location {}", location),
+                    },
+                    format,
+                }
+            })
+            .collect();
+
+        Ok((state, diagnostics))
+    }
+
+    /// Builds the vertex definition like [`definition`](Self::definition), and also checks the
+    /// divisors of instance bindings against the
+    /// [`max_vertex_attrib_divisor`](Properties::max_vertex_attrib_divisor) limit of a device
+    /// with the given `properties`.
+    ///
+    /// If the device doesn't support the `ext_vertex_attribute_divisor` extension, the limit is
+    /// taken to be 1. Whether a divisor of 0 is allowed depends on a device feature, and is not
+    /// checked here.
+    ///
+    /// The default implementation inspects the result of `definition`.
+    fn definition_checked(
+        &self,
+        interface: &ShaderInterface,
+        properties: &Properties,
+    ) -> Result<VertexInputState, IncompatibleVertexDefinitionError> {
+        let state = self.definition(interface)?;
+        let max = properties.max_vertex_attrib_divisor.unwrap_or(1);
+
+        let mut bindings: Vec<_> = state.bindings.iter().collect();
+        bindings.sort_by_key(|&(&binding, _)| binding);
+
+        for (&binding, description) in bindings {
+            if let VertexInputRate::Instance { divisor } = description.input_rate {
+                if divisor > max {
+                    return Err(IncompatibleVertexDefinitionError::DivisorTooLarge {
+                        binding,
+                        max,
+                        obtained: divisor,
+                    });
+                }
+            }
+        }
+
+        Ok(state)
+    }
+}
+
+/// A potential problem with a vertex definition that doesn't prevent it from being used with a
+/// shader. Returned by [`VertexDefinition::definition_with_diagnostics`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VertexDefinitionDiagnostic {
+    /// An attribute has an sRGB format, such as `R8G8B8A8_SRGB`.
+    ///
+    /// sRGB formats are accepted for floating point shader inputs, like the corresponding `UNORM`
+    /// formats. However, vertex input does not apply the sRGB transfer function when the
+    /// attribute is fetched, unlike sampling an image, so the shader receives the stored values
+    /// normalized to \[0, 1\] without conversion to linear color.
+    SrgbAttribute {
+        /// Name of the attribute, or its location if the shader input has no name.
+        attribute: String,
+        /// The format of the attribute in the vertex definition.
+        format: Format,
+    },
+}
+
+impl Display for VertexDefinitionDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            VertexDefinitionDiagnostic::SrgbAttribute { attribute, format } => write!(
+                f,
+                "attribute \"{}\" has the sRGB format {:?}, which is read without sRGB \
+                conversion",
+                attribute, format,
+            ),
+        }
+    }
+}
+
+/// How strictly [`VertexDefinition::definition_with_mode`] matches the vertex definition to the
+/// inputs of a shader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MatchMode {
+    /// Every input of the shader must be provided by the vertex definition. This is the behavior
+    /// of [`VertexDefinition::definition`].
+    Strict,
+
+    /// Like `Strict`, but attributes of the vertex definition that are not read by the shader are
+    /// removed from the result, along with any bindings that no longer have attributes.
+    IgnoreUnusedDefinitionMembers,
+
+    /// Like `IgnoreUnusedDefinitionMembers`, but shader inputs that are missing from the vertex
+    /// definition don't cause an error.
+    ///
+    /// Instead, they are all read from offset 0 of one additional binding, whose description has
+    /// a stride of 0 and [`VertexInputRate::Vertex`], so that every vertex reads the same bytes.
+    /// This binding is numbered one higher than the highest of the other bindings in the result,
+    /// or 0 if there are none, and is only added if an input is missing.
+    ///
+    /// The format of each attribute is a 32-bit format, or a 64-bit format for 64-bit inputs,
+    /// with the numeric type and number of components of the shader input. The largest of these
+    /// formats, `R64G64B64A64_*`, is 32 bytes, so you must bind a vertex buffer containing at
+    /// least 32 zeroed bytes to this binding, so that every missing input reads zeroes.
+    AllowMissingShaderInputs,
+}
+
+impl Default for MatchMode {
+    #[inline]
+    fn default() -> Self {
+        MatchMode::Strict
+    }
+}
+
+/// Returns the 32-bit or 64-bit format matching the numeric type and number of components of
+/// `ty`.
+fn default_input_format(ty: &ShaderInterfaceEntryType) -> Format {
+    if ty.is_64bit {
+        return match (ty.base_type, ty.num_components) {
+            (ShaderScalarType::Float, 1) => Format::R64_SFLOAT,
+            (ShaderScalarType::Float, 2) => Format::R64G64_SFLOAT,
+            (ShaderScalarType::Float, 3) => Format::R64G64B64_SFLOAT,
+            (ShaderScalarType::Float, _) => Format::R64G64B64A64_SFLOAT,
+            (ShaderScalarType::Sint, 1) => Format::R64_SINT,
+            (ShaderScalarType::Sint, 2) => Format::R64G64_SINT,
+            (ShaderScalarType::Sint, 3) => Format::R64G64B64_SINT,
+            (ShaderScalarType::Sint, _) => Format::R64G64B64A64_SINT,
+            (ShaderScalarType::Uint, 1) => Format::R64_UINT,
+            (ShaderScalarType::Uint, 2) => Format::R64G64_UINT,
+            (ShaderScalarType::Uint, 3) => Format::R64G64B64_UINT,
+            (ShaderScalarType::Uint, _) => Format::R64G64B64A64_UINT,
+        };
+    }
+
+    match (ty.base_type, ty.num_components) {
+        (ShaderScalarType::Float, 1) => Format::R32_SFLOAT,
+        (ShaderScalarType::Float, 2) => Format::R32G32_SFLOAT,
+        (ShaderScalarType::Float, 3) => Format::R32G32B32_SFLOAT,
+        (ShaderScalarType::Float, _) => Format::R32G32B32A32_SFLOAT,
+        (ShaderScalarType::Sint, 1) => Format::R32_SINT,
+        (ShaderScalarType::Sint, 2) => Format::R32G32_SINT,
+        (ShaderScalarType::Sint, 3) => Format::R32G32B32_SINT,
+        (ShaderScalarType::Sint, _) => Format::R32G32B32A32_SINT,
+        (ShaderScalarType::Uint, 1) => Format::R32_UINT,
+        (ShaderScalarType::Uint, 2) => Format::R32G32_UINT,
+        (ShaderScalarType::Uint, 3) => Format::R32G32B32_UINT,
+        (ShaderScalarType::Uint, _) => Format::R32G32B32A32_UINT,
+    }
 }
 
 unsafe impl VertexDefinition for VertexInputState {
@@ -79,6 +391,304 @@ unsafe impl VertexDefinition for VertexInputState {
     }
 }
 
+/// Adapter that implements [`VertexDefinition`] for a [`VertexInputState`] by matching the
+/// inputs of the shader to its attributes by location, and checking that their formats are
+/// compatible.
+///
+/// Unlike the definitions that match by name, this works with shaders that were compiled without
+/// reflection names. Attributes of the wrapped state that are not read by the shader are not
+/// included in the resulting definition.
+#[derive(Clone, Debug, Default)]
+pub struct ByLocation(pub VertexInputState);
+
+unsafe impl VertexDefinition for ByLocation {
+    #[inline]
+    fn definition(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<VertexInputState, IncompatibleVertexDefinitionError> {
+        self.definition_all(interface)
+            .map_err(|mut errors| errors.remove(0))
+    }
+
+    fn definition_all(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<VertexInputState, Vec<IncompatibleVertexDefinitionError>> {
+        let elements = interface.elements();
+        let mut attributes = HashMap::default();
+        let mut errors = Vec::new();
+
+        for (index, element) in elements.iter().enumerate() {
+            let locations = element.location..element.location + element.ty.num_locations();
+
+            if let Some(location) = elements[..index].iter().find_map(|other| {
+                let other_locations = other.location..other.location + other.ty.num_locations();
+                let components_overlap = element.component
+                    < other.component + other.ty.num_components
+                    && other.component < element.component + element.ty.num_components;
+
+                (components_overlap
+                    && locations.start < other_locations.end
+                    && other_locations.start < locations.end)
+                    .then(|| locations.start.max(other_locations.start))
+            }) {
+                errors.push(IncompatibleVertexDefinitionError::LocationCollision { location });
+            }
+        }
+
+        // Inputs that collide can't be matched meaningfully.
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        for element in elements {
+            let locations = (element.location..element.location + element.ty.num_locations())
+                .step_by(element.ty.locations_per_element() as usize);
+
+            for location in locations {
+                let attribute = match self.0.attributes.get(&location) {
+                    Some(attribute) => attribute,
+                    None => {
+                        errors
+                            .push(IncompatibleVertexDefinitionError::MissingLocation { location });
+                        continue;
+                    }
+                };
+                let infos = VertexMemberInfo {
+                    offset: attribute.offset as usize,
+                    format: attribute.format,
+                    num_elements: 1,
+                };
+
+                if !format_matches_shader_type(infos.format, &element.ty) {
+                    errors.push(IncompatibleVertexDefinitionError::FormatMismatch {
+                        attribute: match element.name.as_deref() {
+                            Some(name) if !name.is_empty() => name.to_owned(),
+                            _ => format!("location {}", location),
+                        },
+                        location,
+                        shader: element.ty,
+                        definition: infos,
+                    });
+                    continue;
+                }
+
+                attributes.insert(location, *attribute);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let bindings = self
+            .0
+            .bindings
+            .iter()
+            .filter(|(binding, _)| attributes.values().any(|a| a.binding == **binding))
+            .map(|(&binding, description)| (binding, description.clone()))
+            .collect();
+
+        Ok(VertexInputState {
+            bindings,
+            attributes,
+        })
+    }
+}
+
+/// Returns whether an attribute with the given format can be used for a shader input of type
+/// `ty`.
+///
+/// The numeric type of the format must belong to the same family as the base type of the input:
+/// `SINT` for signed integers, `UINT` for unsigned integers, and any other numeric type for
+/// floating point. The format must have the same number of components as the input, and must be
+/// 64-bit if and only if the input is.
+///
+/// sRGB formats match floating point inputs, but are not converted to linear color when they are
+/// fetched. [`VertexDefinition::definition_with_diagnostics`] reports such attributes.
+pub fn format_matches_shader_type(format: Format, ty: &ShaderInterfaceEntryType) -> bool {
+    let numeric_type = match format.numeric_type() {
+        Some(numeric_type) => numeric_type,
+        None => return false,
+    };
+    let is_64bit = format.components()[0] == 64;
+
+    ShaderScalarType::from(numeric_type) == ty.base_type
+        && format.component_count() as u32 == ty.num_components
+        && is_64bit == ty.is_64bit
+}
+
+/// Creates a `VertexInputState` that provides every input of `interface` from a single binding
+/// with a per-vertex input rate.
+///
+/// This is useful for prototyping, when the vertex data mirrors the inputs of the shader exactly.
+/// The inputs are assumed to be interleaved in order of their locations, and tightly packed. The
+/// format of each input is a 32-bit format, or a 64-bit format for 64-bit inputs, with the numeric
+/// type and number of components of the input. For example, a `vec3` input uses
+/// `R32G32B32_SFLOAT`, and an `ivec2` input uses `R32G32_SINT`. Arrays and matrices use one
+/// attribute for each location they occupy.
+pub fn auto_definition(interface: &ShaderInterface) -> VertexInputState {
+    let mut elements: Vec<_> = interface.entries().collect();
+    elements.sort_by_key(|element| element.location);
+
+    let mut attributes = Vec::new();
+    let mut offset = 0;
+
+    for element in elements {
+        let format = default_input_format(&element.ty);
+        let locations = (element.location..element.location + element.ty.num_locations())
+            .step_by(element.ty.locations_per_element() as usize);
+
+        for location in locations {
+            attributes.push((
+                location,
+                VertexInputAttributeDescription {
+                    binding: 0,
+                    format,
+                    offset,
+                },
+            ));
+            offset += format.block_size().unwrap() as u32;
+        }
+    }
+
+    VertexInputState::new()
+        .binding(
+            0,
+            VertexInputBindingDescription {
+                stride: offset,
+                input_rate: VertexInputRate::Vertex,
+            },
+        )
+        .attributes(attributes)
+}
+
+/// Matches every element of `interface` with a vertex member of the same name, and returns the
+/// attribute descriptions for the locations they occupy.
+///
+/// `find_member` returns the member with the given name, along with the binding it belongs to.
+/// `member_names` returns the names of all members, and is only called if an attribute is missing
+/// to suggest a similar name. Every element is checked, and all errors are returned.
+pub(super) fn attributes_by_name(
+    interface: &ShaderInterface,
+    mut find_member: impl FnMut(&str) -> Option<(VertexMemberInfo, u32)>,
+    member_names: impl FnOnce() -> Vec<String>,
+) -> Result<Vec<(u32, VertexInputAttributeDescription)>, Vec<IncompatibleVertexDefinitionError>> {
+    let mut attributes: Vec<(u32, VertexInputAttributeDescription)> = Vec::new();
+    let mut errors = Vec::new();
+    let mut member_names = Some(member_names);
+    let mut names = Vec::new();
+
+    for element in interface.entries() {
+        let name = element.name.as_ref().unwrap();
+
+        let (infos, binding) = match find_member(name) {
+            Some(member) => member,
+            None => {
+                if let Some(member_names) = member_names.take() {
+                    names = member_names();
+                }
+
+                // TODO: move this check to GraphicsPipelineBuilder
+                errors.push(IncompatibleVertexDefinitionError::MissingAttribute {
+                    attribute: name.clone().into_owned(),
+                    location: element.location,
+                    suggestion: closest_name(name, &names).map(ToOwned::to_owned),
+                    shader_inputs: interface
+                        .entries()
+                        .filter_map(|element| element.name.as_deref())
+                        .map(ToOwned::to_owned)
+                        .collect(),
+                });
+                continue;
+            }
+        };
+
+        if !format_matches_shader_type(infos.format, &element.ty) {
+            errors.push(IncompatibleVertexDefinitionError::FormatMismatch {
+                attribute: name.clone().into_owned(),
+                location: element.location,
+                shader: element.ty,
+                definition: infos,
+            });
+            continue;
+        }
+
+        // The format matches, so each element takes up as many locations in the vertex
+        // definition as in the shader.
+        let locations_per_element = element.ty.locations_per_element();
+
+        if infos.num_elements != element.ty.num_elements {
+            errors.push(IncompatibleVertexDefinitionError::LocationOutOfRange {
+                attribute: name.clone().into_owned(),
+                shader_locations: element.ty.num_locations(),
+                provided_locations: infos.num_elements * locations_per_element,
+            });
+            continue;
+        }
+
+        let mut offset = infos.offset as DeviceSize;
+        let block_size = infos.format.block_size().unwrap();
+        // 64-bit formats with more than two components span two locations, but the attribute is
+        // only specified at the first of them.
+        let location_range = (element.location..element.location + element.ty.num_locations())
+            .step_by(locations_per_element as usize);
+
+        for location in location_range {
+            attributes.push((
+                location,
+                VertexInputAttributeDescription {
+                    binding,
+                    format: infos.format,
+                    offset: offset as u32,
+                },
+            ));
+            offset += block_size;
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(attributes)
+}
+
+// Returns the name in `names` that is most similar to `name`, if any is similar enough to be a
+// likely typo.
+fn closest_name<'a>(name: &str, names: &'a [String]) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    names
+        .iter()
+        .map(|candidate| (levenshtein_distance(name, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+// The number of single-character insertions, deletions and substitutions needed to turn `a` into
+// `b`.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // The distances between the processed prefix of `a` and each prefix of `b`.
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = diagonal + (a_char != b_char) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
 /// Error that can happen when the vertex definition doesn't match the input of the vertex shader.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum IncompatibleVertexDefinitionError {
@@ -86,17 +696,61 @@ pub enum IncompatibleVertexDefinitionError {
     MissingAttribute {
         /// Name of the missing attribute.
         attribute: String,
+        /// The first location of the input in the vertex shader.
+        location: u32,
+        /// Names of all inputs declared by the vertex shader.
+        shader_inputs: Vec<String>,
+        /// The name of a member of the vertex definition that is similar to the missing
+        /// attribute, if any.
+        suggestion: Option<String>,
     },
 
     /// The format of an attribute does not match.
     FormatMismatch {
         /// Name of the attribute.
         attribute: String,
+        /// The location of the input in the vertex shader. For inputs that occupy several
+        /// locations, this is the first location.
+        location: u32,
         /// The format in the vertex shader.
         shader: ShaderInterfaceEntryType,
         /// The format in the vertex definition.
         definition: VertexMemberInfo,
     },
+
+    /// The format of an attribute matches, but the vertex definition provides data for a
+    /// different number of locations than the shader input occupies.
+    LocationOutOfRange {
+        /// Name of the attribute.
+        attribute: String,
+        /// The number of locations occupied by the input in the vertex shader.
+        shader_locations: u32,
+        /// The number of locations provided by the vertex definition.
+        provided_locations: u32,
+    },
+
+    /// The vertex shader reads from a location that has no attribute in the vertex definition.
+    MissingLocation {
+        /// The location that is missing.
+        location: u32,
+    },
+
+    /// Two inputs of the vertex shader occupy the same components of a location.
+    LocationCollision {
+        /// The first location that is occupied by both inputs.
+        location: u32,
+    },
+
+    /// The divisor of an instance binding exceeds the `max_vertex_attrib_divisor` limit of the
+    /// device.
+    DivisorTooLarge {
+        /// The binding with the divisor.
+        binding: u32,
+        /// The largest divisor the device supports.
+        max: u32,
+        /// The divisor of the binding.
+        obtained: u32,
+    },
 }
 
 impl Error for IncompatibleVertexDefinitionError {}
@@ -104,12 +758,483 @@ impl Error for IncompatibleVertexDefinitionError {}
 impl Display for IncompatibleVertexDefinitionError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         match self {
-            IncompatibleVertexDefinitionError::MissingAttribute { .. } => {
-                write!(f, "an attribute is missing")
-            }
-            IncompatibleVertexDefinitionError::FormatMismatch { .. } => {
-                write!(f, "the format of an attribute does not match")
+            IncompatibleVertexDefinitionError::MissingAttribute {
+                attribute,
+                location,
+                shader_inputs,
+                suggestion,
+            } => {
+                write!(
+                    f,
+                    "attribute \"{}\" at location {} required by the shader is missing from the \
+                    vertex definition",
+                    attribute, location,
+                )?;
+
+                if !shader_inputs.is_empty() {
+                    write!(f, "; shader declares: {}", shader_inputs.join(", "))?;
+                }
+
+                if let Some(suggestion) = suggestion {
+                    write!(f, "; did you mean \"{}\"?", suggestion)?;
+                }
+
+                Ok(())
             }
+            IncompatibleVertexDefinitionError::FormatMismatch {
+                attribute,
+                location,
+                shader,
+                definition,
+            } => write!(
+                f,
+                "attribute \"{}\" at location {} has format {:?} in the shader but {:?} in the \
+                vertex definition",
+                attribute, location, shader, definition,
+            ),
+            IncompatibleVertexDefinitionError::LocationOutOfRange {
+                attribute,
+                shader_locations,
+                provided_locations,
+            } => write!(
+                f,
+                "attribute \"{}\" occupies {} locations in the shader but the vertex definition \
+                provides {}",
+                attribute, shader_locations, provided_locations,
+            ),
+            IncompatibleVertexDefinitionError::MissingLocation { location } => write!(
+                f,
+                "location {} is read by the shader but has no attribute in the vertex definition",
+                location,
+            ),
+            IncompatibleVertexDefinitionError::LocationCollision { location } => write!(
+                f,
+                "multiple shader inputs occupy the same components of location {}",
+                location,
+            ),
+            IncompatibleVertexDefinitionError::DivisorTooLarge {
+                binding,
+                max,
+                obtained,
+            } => write!(
+                f,
+                "the divisor of binding {} is {}, which exceeds the `max_vertex_attrib_divisor` \
+                limit of {}",
+                binding, obtained, max,
+            ),
         }
     }
 }
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::{
+        closest_name, format_matches_shader_type, levenshtein_distance, ByLocation,
+        IncompatibleVertexDefinitionError, MatchMode, VertexDefinition, VertexDefinitionDiagnostic,
+    };
+    use crate::{
+        device::Properties,
+        format::Format,
+        pipeline::graphics::vertex_input::{
+            VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate,
+            VertexInputState,
+        },
+        shader::{
+            ShaderInterface, ShaderInterfaceEntry, ShaderInterfaceEntryType, ShaderScalarType,
+        },
+    };
+    use std::borrow::Cow;
+
+    pub(in crate::pipeline::graphics::vertex_input) fn shader_interface(
+        inputs: &[(&'static str, u32, ShaderInterfaceEntryType)],
+    ) -> ShaderInterface {
+        let elements = inputs
+            .iter()
+            .map(|&(name, location, ty)| ShaderInterfaceEntry {
+                location,
+                component: 0,
+                name: Some(Cow::Borrowed(name)),
+                ty,
+            })
+            .collect();
+
+        unsafe { ShaderInterface::new_unchecked(elements) }
+    }
+
+    pub(in crate::pipeline::graphics::vertex_input) fn float_type(
+        num_components: u32,
+        num_elements: u32,
+    ) -> ShaderInterfaceEntryType {
+        ShaderInterfaceEntryType {
+            base_type: ShaderScalarType::Float,
+            num_components,
+            num_elements,
+            is_64bit: false,
+        }
+    }
+
+    #[test]
+    fn by_location() {
+        let state = VertexInputState::new()
+            .binding(
+                0,
+                VertexInputBindingDescription {
+                    stride: 20,
+                    input_rate: VertexInputRate::Vertex,
+                },
+            )
+            .binding(
+                1,
+                VertexInputBindingDescription {
+                    stride: 4,
+                    input_rate: VertexInputRate::Vertex,
+                },
+            )
+            .attribute(
+                0,
+                VertexInputAttributeDescription {
+                    binding: 0,
+                    format: Format::R32G32B32_SFLOAT,
+                    offset: 0,
+                },
+            )
+            .attribute(
+                1,
+                VertexInputAttributeDescription {
+                    binding: 0,
+                    format: Format::R32G32_SFLOAT,
+                    offset: 12,
+                },
+            )
+            .attribute(
+                2,
+                VertexInputAttributeDescription {
+                    binding: 1,
+                    format: Format::R32_SFLOAT,
+                    offset: 0,
+                },
+            );
+        let definition = ByLocation(state);
+
+        let interface = shader_interface(&[("", 0, float_type(3, 1)), ("", 1, float_type(2, 1))]);
+        let result = definition.definition(&interface).unwrap();
+        assert_eq!(result.attributes.len(), 2);
+        assert_eq!(result.attributes[&1].offset, 12);
+        assert_eq!(result.bindings.len(), 1);
+        assert_eq!(result.bindings[&0].stride, 20);
+
+        let interface = shader_interface(&[("", 3, float_type(1, 1))]);
+        assert_eq!(
+            definition.definition(&interface).unwrap_err(),
+            IncompatibleVertexDefinitionError::MissingLocation { location: 3 },
+        );
+
+        let interface = shader_interface(&[("", 1, float_type(3, 1))]);
+        assert!(matches!(
+            definition.definition(&interface),
+            Err(IncompatibleVertexDefinitionError::FormatMismatch { attribute, .. })
+                if attribute == "location 1",
+        ));
+
+        let interface = shader_interface(&[("", 0, float_type(3, 2)), ("", 1, float_type(2, 1))]);
+        assert_eq!(
+            definition.definition(&interface).unwrap_err(),
+            IncompatibleVertexDefinitionError::LocationCollision { location: 1 },
+        );
+    }
+
+    #[test]
+    fn srgb_diagnostics() {
+        let attribute = |format| VertexInputAttributeDescription {
+            binding: 0,
+            format,
+            offset: 0,
+        };
+        let state = VertexInputState::new()
+            .binding(
+                0,
+                VertexInputBindingDescription {
+                    stride: 8,
+                    input_rate: VertexInputRate::Vertex,
+                },
+            )
+            .attribute(0, attribute(Format::R8G8B8A8_UNORM))
+            .attribute(1, attribute(Format::R8G8B8A8_SRGB));
+        let definition = ByLocation(state);
+
+        assert!(Format::R8G8B8A8_SRGB.is_srgb());
+        assert!(Format::BC1_RGB_SRGB_BLOCK.is_srgb());
+        assert!(!Format::R8G8B8A8_UNORM.is_srgb());
+
+        let interface = shader_interface(&[
+            ("color", 0, float_type(4, 1)),
+            ("tint", 1, float_type(4, 1)),
+        ]);
+        let (state, diagnostics) = definition.definition_with_diagnostics(&interface).unwrap();
+        assert_eq!(state.attributes.len(), 2);
+        assert_eq!(
+            diagnostics,
+            [VertexDefinitionDiagnostic::SrgbAttribute {
+                attribute: "tint".to_owned(),
+                format: Format::R8G8B8A8_SRGB,
+            }],
+        );
+    }
+
+    #[test]
+    fn definition_checked() {
+        let state = VertexInputState::new()
+            .binding(
+                0,
+                VertexInputBindingDescription {
+                    stride: 16,
+                    input_rate: VertexInputRate::Vertex,
+                },
+            )
+            .binding(
+                1,
+                VertexInputBindingDescription {
+                    stride: 16,
+                    input_rate: VertexInputRate::Instance { divisor: 4 },
+                },
+            )
+            .attribute(
+                0,
+                VertexInputAttributeDescription {
+                    binding: 0,
+                    format: Format::R32G32B32A32_SFLOAT,
+                    offset: 0,
+                },
+            )
+            .attribute(
+                1,
+                VertexInputAttributeDescription {
+                    binding: 1,
+                    format: Format::R32G32B32A32_SFLOAT,
+                    offset: 0,
+                },
+            );
+        let definition = ByLocation(state);
+        let interface = shader_interface(&[
+            ("position", 0, float_type(4, 1)),
+            ("offset", 1, float_type(4, 1)),
+        ]);
+
+        let properties = Properties {
+            max_vertex_attrib_divisor: Some(4),
+            ..Default::default()
+        };
+        assert!(definition
+            .definition_checked(&interface, &properties)
+            .is_ok());
+
+        let properties = Properties {
+            max_vertex_attrib_divisor: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            definition
+                .definition_checked(&interface, &properties)
+                .unwrap_err(),
+            IncompatibleVertexDefinitionError::DivisorTooLarge {
+                binding: 1,
+                max: 3,
+                obtained: 4,
+            },
+        );
+
+        // Without the extension, only a divisor of 1 is allowed.
+        assert!(matches!(
+            definition.definition_checked(&interface, &Properties::default()),
+            Err(IncompatibleVertexDefinitionError::DivisorTooLarge { max: 1, .. }),
+        ));
+    }
+
+    #[test]
+    fn auto_definition() {
+        let interface = shader_interface(&[
+            ("uv", 3, float_type(2, 1)),
+            ("position", 0, float_type(3, 1)),
+            ("transform", 4, float_type(4, 2)),
+            (
+                "index",
+                1,
+                ShaderInterfaceEntryType {
+                    base_type: ShaderScalarType::Uint,
+                    num_components: 1,
+                    num_elements: 1,
+                    is_64bit: false,
+                },
+            ),
+        ]);
+
+        let state = super::auto_definition(&interface);
+        assert_eq!(state.bindings.len(), 1);
+        assert_eq!(state.bindings[&0].stride, 56);
+
+        let mut attributes: Vec<_> = state
+            .attributes
+            .iter()
+            .map(|(&location, attribute)| (location, attribute.format, attribute.offset))
+            .collect();
+        attributes.sort_unstable_by_key(|&(location, _, _)| location);
+        assert_eq!(
+            attributes,
+            [
+                (0, Format::R32G32B32_SFLOAT, 0),
+                (1, Format::R32_UINT, 12),
+                (3, Format::R32G32_SFLOAT, 16),
+                (4, Format::R32G32B32A32_SFLOAT, 24),
+                (5, Format::R32G32B32A32_SFLOAT, 40),
+            ],
+        );
+        assert_eq!(
+            ByLocation(state)
+                .definition(&interface)
+                .unwrap()
+                .attributes
+                .len(),
+            5
+        );
+    }
+
+    #[test]
+    fn definition_with_mode() {
+        let state = VertexInputState::new()
+            .binding(
+                0,
+                VertexInputBindingDescription {
+                    stride: 12,
+                    input_rate: VertexInputRate::Vertex,
+                },
+            )
+            .binding(
+                1,
+                VertexInputBindingDescription {
+                    stride: 8,
+                    input_rate: VertexInputRate::Vertex,
+                },
+            )
+            .attribute(
+                0,
+                VertexInputAttributeDescription {
+                    binding: 0,
+                    format: Format::R32G32B32_SFLOAT,
+                    offset: 0,
+                },
+            )
+            .attribute(
+                1,
+                VertexInputAttributeDescription {
+                    binding: 1,
+                    format: Format::R32G32_SFLOAT,
+                    offset: 0,
+                },
+            );
+        let definition = ByLocation(state.clone());
+
+        let interface = shader_interface(&[("", 0, float_type(3, 1)), ("", 2, float_type(4, 1))]);
+        assert_eq!(
+            definition
+                .definition_with_mode(&interface, MatchMode::Strict)
+                .unwrap_err(),
+            IncompatibleVertexDefinitionError::MissingLocation { location: 2 },
+        );
+
+        let result = definition
+            .definition_with_mode(&interface, MatchMode::AllowMissingShaderInputs)
+            .unwrap();
+        assert_eq!(result.bindings.len(), 2);
+        assert_eq!(result.bindings[&1].stride, 0);
+        assert_eq!(result.attributes[&0].binding, 0);
+        assert_eq!(result.attributes[&2].binding, 1);
+        assert_eq!(result.attributes[&2].format, Format::R32G32B32A32_SFLOAT);
+
+        // All missing inputs share the same binding.
+        let interface = shader_interface(&[
+            ("", 0, float_type(3, 1)),
+            ("", 2, float_type(4, 1)),
+            ("", 3, float_type(2, 1)),
+        ]);
+        let result = definition
+            .definition_with_mode(&interface, MatchMode::AllowMissingShaderInputs)
+            .unwrap();
+        assert_eq!(result.bindings.len(), 2);
+        assert_eq!(result.attributes[&2].binding, 1);
+        assert_eq!(result.attributes[&3].binding, 1);
+        assert_eq!(result.attributes[&3].format, Format::R32G32_SFLOAT);
+
+        let interface = shader_interface(&[("", 0, float_type(3, 1))]);
+        let result = state
+            .definition_with_mode(&interface, MatchMode::Strict)
+            .unwrap();
+        assert_eq!(result.attributes.len(), 2);
+        let result = state
+            .definition_with_mode(&interface, MatchMode::IgnoreUnusedDefinitionMembers)
+            .unwrap();
+        assert_eq!(result.attributes.len(), 1);
+        assert_eq!(result.bindings.len(), 1);
+    }
+
+    #[test]
+    fn format_matches() {
+        let int_type = ShaderInterfaceEntryType {
+            base_type: ShaderScalarType::Sint,
+            ..float_type(2, 1)
+        };
+        let double_type = ShaderInterfaceEntryType {
+            is_64bit: true,
+            ..float_type(2, 1)
+        };
+
+        assert!(format_matches_shader_type(
+            Format::R32G32_SFLOAT,
+            &float_type(2, 1)
+        ));
+        assert!(format_matches_shader_type(
+            Format::R8G8_UNORM,
+            &float_type(2, 1)
+        ));
+        assert!(format_matches_shader_type(Format::R16G16_SINT, &int_type));
+        assert!(format_matches_shader_type(
+            Format::R64G64_SFLOAT,
+            &double_type
+        ));
+
+        assert!(!format_matches_shader_type(
+            Format::R32G32B32_SFLOAT,
+            &float_type(2, 1)
+        ));
+        assert!(!format_matches_shader_type(Format::R32G32_UINT, &int_type));
+        assert!(!format_matches_shader_type(
+            Format::R32G32_SINT,
+            &float_type(2, 1)
+        ));
+        assert!(!format_matches_shader_type(
+            Format::R32G32_SFLOAT,
+            &double_type
+        ));
+        assert!(!format_matches_shader_type(
+            Format::D32_SFLOAT,
+            &float_type(1, 1)
+        ));
+        assert!(!format_matches_shader_type(
+            Format::BC4_UNORM_BLOCK,
+            &float_type(1, 1)
+        ));
+    }
+
+    #[test]
+    fn suggestion() {
+        assert_eq!(levenshtein_distance("position", "position"), 0);
+        assert_eq!(levenshtein_distance("postion", "position"), 1);
+        assert_eq!(levenshtein_distance("uv", "normal"), 6);
+        assert_eq!(levenshtein_distance("", "uv"), 2);
+
+        let names = ["position".to_owned(), "normal".to_owned(), "uv".to_owned()];
+        assert_eq!(closest_name("postion", &names), Some("position"));
+        assert_eq!(closest_name("nromal", &names), Some("normal"));
+        assert_eq!(closest_name("color", &names), None);
+        assert_eq!(closest_name("uv", &[]), None);
+    }
+}
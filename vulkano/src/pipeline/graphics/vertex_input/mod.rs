@@ -102,21 +102,39 @@
 pub use self::{
     buffers::BuffersDefinition,
     collection::VertexBuffersCollection,
-    definition::{IncompatibleVertexDefinitionError, VertexDefinition},
+    definition::{
+        auto_definition, format_matches_shader_type, ByLocation, IncompatibleVertexDefinitionError,
+        MatchMode, VertexDefinition, VertexDefinitionDiagnostic,
+    },
     impl_vertex::VertexMember,
+    runtime::{
+        deinterleave, validate_attributes, BorrowedVertexInput, DeinterleaveError, Endianness,
+        GeneratedVertexBuilder, GeneratedVertexIter, IntegerAlignment, InterleavedVertexData,
+        OwnedRuntimeVertexBuilder, OwnedRuntimeVertexIter, PadMode, RuntimeVertex,
+        RuntimeVertexBufferError, RuntimeVertexBuildError, RuntimeVertexBuilder, RuntimeVertexIter,
+        RuntimeVertexWriteError, VertexAttribute, VertexBufferInfo,
+    },
     vertex::{Vertex, VertexMemberInfo},
 };
-use crate::format::Format;
+use crate::{device::Properties, format::Format};
 use ahash::HashMap;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+};
 
 mod buffers;
 mod collection;
 mod definition;
 mod impl_vertex;
+mod runtime;
 mod vertex;
 
 /// The state in a graphics pipeline describing how the vertex input stage should behave.
-#[derive(Clone, Debug, Default)]
+///
+/// Bindings and attributes are keyed by their number and location, so two states compare equal
+/// regardless of the order in which they were added.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct VertexInputState {
     /// A description of the vertex buffers that the vertex input stage will read from.
     pub bindings: HashMap<u32, VertexInputBindingDescription>,
@@ -171,10 +189,327 @@ impl VertexInputState {
         self.attributes = attributes.into_iter().collect();
         self
     }
+
+    /// Combines the bindings and attributes of `self` and `other` into a single state.
+    ///
+    /// The bindings of `self` keep their numbers. The bindings of `other` keep their numbers
+    /// too, unless the number is already taken, in which case they are renumbered to the lowest
+    /// number above every binding taken so far. The attributes of `other` are updated to refer to
+    /// the new binding numbers. The stride and input rate of every binding are preserved.
+    ///
+    /// Returns an error if both states have an attribute at the same location.
+    pub fn merge(mut self, other: VertexInputState) -> Result<Self, BindingCollisionError> {
+        if let Some(&location) = other
+            .attributes
+            .keys()
+            .filter(|location| self.attributes.contains_key(location))
+            .min()
+        {
+            return Err(BindingCollisionError { location });
+        }
+
+        let mut other_bindings: Vec<_> = other.bindings.into_iter().collect();
+        other_bindings.sort_by_key(|&(binding, _)| binding);
+        let mut renumbered = HashMap::default();
+
+        for (binding, description) in other_bindings {
+            let new_binding = if self.bindings.contains_key(&binding) {
+                self.bindings.keys().max().unwrap() + 1
+            } else {
+                binding
+            };
+
+            self.bindings.insert(new_binding, description);
+            renumbered.insert(binding, new_binding);
+        }
+
+        for (location, mut description) in other.attributes {
+            if let Some(&binding) = renumbered.get(&description.binding) {
+                description.binding = binding;
+            }
+
+            self.attributes.insert(location, description);
+        }
+
+        Ok(self)
+    }
+
+    /// Checks that the bindings and attributes of `self` stay within the vertex input limits of
+    /// a device with the given `properties`.
+    ///
+    /// The checked limits are [`max_vertex_input_bindings`], [`max_vertex_input_attributes`],
+    /// [`max_vertex_input_binding_stride`] and [`max_vertex_input_attribute_offset`]. If several
+    /// limits are exceeded, only the first one is reported, in that order.
+    ///
+    /// [`max_vertex_input_bindings`]: crate::device::Properties::max_vertex_input_bindings
+    /// [`max_vertex_input_attributes`]: crate::device::Properties::max_vertex_input_attributes
+    /// [`max_vertex_input_binding_stride`]: crate::device::Properties::max_vertex_input_binding_stride
+    /// [`max_vertex_input_attribute_offset`]: crate::device::Properties::max_vertex_input_attribute_offset
+    pub fn validate_against_limits(
+        &self,
+        properties: &Properties,
+    ) -> Result<(), VertexInputLimitError> {
+        // Both the number of bindings and the binding numbers must be below the limit.
+        let bindings_needed = self
+            .bindings
+            .keys()
+            .map(|&binding| binding.saturating_add(1))
+            .max()
+            .unwrap_or(0)
+            .max(self.bindings.len() as u32);
+
+        if bindings_needed > properties.max_vertex_input_bindings {
+            return Err(VertexInputLimitError::MaxVertexInputBindingsExceeded {
+                max: properties.max_vertex_input_bindings,
+                obtained: bindings_needed,
+            });
+        }
+
+        // Likewise for attributes and their locations.
+        let attributes_needed = self
+            .attributes
+            .keys()
+            .map(|&location| location.saturating_add(1))
+            .max()
+            .unwrap_or(0)
+            .max(self.attributes.len() as u32);
+
+        if attributes_needed > properties.max_vertex_input_attributes {
+            return Err(VertexInputLimitError::MaxVertexInputAttributesExceeded {
+                max: properties.max_vertex_input_attributes,
+                obtained: attributes_needed,
+            });
+        }
+
+        let mut bindings: Vec<_> = self.bindings.iter().collect();
+        bindings.sort_by_key(|&(&binding, _)| binding);
+
+        for (&binding, description) in bindings {
+            if description.stride > properties.max_vertex_input_binding_stride {
+                return Err(VertexInputLimitError::MaxVertexInputBindingStrideExceeded {
+                    binding,
+                    max: properties.max_vertex_input_binding_stride,
+                    obtained: description.stride,
+                });
+            }
+        }
+
+        let mut attributes: Vec<_> = self.attributes.iter().collect();
+        attributes.sort_by_key(|&(&location, _)| location);
+
+        for (&location, description) in attributes {
+            if description.offset > properties.max_vertex_input_attribute_offset {
+                return Err(
+                    VertexInputLimitError::MaxVertexInputAttributeOffsetExceeded {
+                        location,
+                        max: properties.max_vertex_input_attribute_offset,
+                        obtained: description.offset,
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns a hash of the bindings and attributes of `self`, for example to look up pipelines
+    /// that were created with the same vertex input layout.
+    ///
+    /// Only the binary layout is hashed: the stride and input rate of every binding, and the
+    /// location, binding, format and offset of every attribute. States that compare equal have
+    /// the same hash, regardless of the order in which their bindings and attributes were added.
+    ///
+    /// The hash doesn't depend on the platform or on the program run, but it may change between
+    /// versions of vulkano.
+    pub fn layout_hash(&self) -> u64 {
+        // FNV-1a, which unlike the hashers of the standard library has a fixed output.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut write = |value: u32| {
+            for byte in value.to_le_bytes() {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        };
+
+        let mut bindings: Vec<_> = self.bindings.iter().collect();
+        bindings.sort_unstable_by_key(|&(&binding, _)| binding);
+        write(bindings.len() as u32);
+
+        for (&binding, description) in bindings {
+            write(binding);
+            write(description.stride);
+
+            match description.input_rate {
+                VertexInputRate::Vertex => write(0),
+                VertexInputRate::Instance { divisor } => {
+                    write(1);
+                    write(divisor);
+                }
+            }
+        }
+
+        let mut attributes: Vec<_> = self.attributes.iter().collect();
+        attributes.sort_unstable_by_key(|&(&location, _)| location);
+        write(attributes.len() as u32);
+
+        for (&location, description) in attributes {
+            write(location);
+            write(description.binding);
+            write(description.format as u32);
+            write(description.offset);
+        }
+
+        hash
+    }
+
+    /// Returns whether `self` and `other` have the same binary layout, so that vertex buffers
+    /// built for one can be used with a pipeline that was created with the other.
+    ///
+    /// This compares the stride and input rate of every binding, and the binding, format and
+    /// offset of the attribute at every location. A `VertexInputState` doesn't record the names
+    /// of the shader inputs, so states that were built from attributes with different names are
+    /// compatible as long as their locations match. Compatible states have the same
+    /// [`layout_hash`](Self::layout_hash).
+    #[inline]
+    pub fn layout_compatible(&self, other: &VertexInputState) -> bool {
+        self.bindings == other.bindings && self.attributes == other.attributes
+    }
+
+    /// Returns the attributes of `self` as a list of [`VertexAttribute`]s, sorted by location.
+    ///
+    /// This is the inverse of [`RuntimeVertexBuilder::into_vertex_input_state`]: every attribute
+    /// description becomes a `VertexAttribute` with the same format and offset. A
+    /// `VertexInputState` doesn't record the names of the shader inputs, so the attributes are
+    /// named `location_N` after their location. Attributes that span several locations in the
+    /// original layout, such as arrays and matrices, are returned as one attribute per location.
+    /// The bindings of the attributes aren't part of the result.
+    pub fn to_attributes(&self) -> Vec<VertexAttribute> {
+        let mut attributes: Vec<_> = self.attributes.iter().collect();
+        attributes.sort_unstable_by_key(|&(&location, _)| location);
+
+        attributes
+            .into_iter()
+            .map(|(&location, description)| VertexAttribute {
+                name: format!("location_{}", location).into(),
+                format: description.format,
+                num_elements: None,
+                offset: Some(description.offset as usize),
+            })
+            .collect()
+    }
+}
+
+/// Error that can happen when merging two [`VertexInputState`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BindingCollisionError {
+    /// The lowest attribute location that is present in both states.
+    pub location: u32,
+}
+
+impl Error for BindingCollisionError {}
+
+impl Display for BindingCollisionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "both vertex input states have an attribute at location {}",
+            self.location,
+        )
+    }
+}
+
+/// Error that can happen when validating a [`VertexInputState`] against the limits of a device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VertexInputLimitError {
+    /// The state needs more bindings than the device supports. `obtained` is the number of
+    /// bindings, or one more than the highest binding number, whichever is greater.
+    MaxVertexInputBindingsExceeded { max: u32, obtained: u32 },
+
+    /// The state needs more attributes than the device supports. `obtained` is the number of
+    /// attributes, or one more than the highest location, whichever is greater.
+    MaxVertexInputAttributesExceeded { max: u32, obtained: u32 },
+
+    /// The stride of a binding is greater than the device supports.
+    MaxVertexInputBindingStrideExceeded {
+        binding: u32,
+        max: u32,
+        obtained: u32,
+    },
+
+    /// The offset of an attribute is greater than the device supports.
+    MaxVertexInputAttributeOffsetExceeded {
+        location: u32,
+        max: u32,
+        obtained: u32,
+    },
+}
+
+impl VertexInputLimitError {
+    /// Returns by how much the limit was exceeded.
+    #[inline]
+    pub fn excess(&self) -> u32 {
+        match *self {
+            Self::MaxVertexInputBindingsExceeded { max, obtained }
+            | Self::MaxVertexInputAttributesExceeded { max, obtained }
+            | Self::MaxVertexInputBindingStrideExceeded { max, obtained, .. }
+            | Self::MaxVertexInputAttributeOffsetExceeded { max, obtained, .. } => obtained - max,
+        }
+    }
+}
+
+impl Error for VertexInputLimitError {}
+
+impl Display for VertexInputLimitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match *self {
+            Self::MaxVertexInputBindingsExceeded { max, obtained } => write!(
+                f,
+                "the state needs {} vertex input bindings, which exceeds the \
+                `max_vertex_input_bindings` limit of {} by {}",
+                obtained,
+                max,
+                self.excess(),
+            ),
+            Self::MaxVertexInputAttributesExceeded { max, obtained } => write!(
+                f,
+                "the state needs {} vertex input attributes, which exceeds the \
+                `max_vertex_input_attributes` limit of {} by {}",
+                obtained,
+                max,
+                self.excess(),
+            ),
+            Self::MaxVertexInputBindingStrideExceeded {
+                binding,
+                max,
+                obtained,
+            } => write!(
+                f,
+                "the stride of binding {} is {}, which exceeds the \
+                `max_vertex_input_binding_stride` limit of {} by {}",
+                binding,
+                obtained,
+                max,
+                self.excess(),
+            ),
+            Self::MaxVertexInputAttributeOffsetExceeded {
+                location,
+                max,
+                obtained,
+            } => write!(
+                f,
+                "the offset of the attribute at location {} is {}, which exceeds the \
+                `max_vertex_input_attribute_offset` limit of {} by {}",
+                location,
+                obtained,
+                max,
+                self.excess(),
+            ),
+        }
+    }
 }
 
 /// Describes a single vertex buffer binding.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct VertexInputBindingDescription {
     /// The number of bytes from the start of one element in the vertex buffer to the start of the
     /// next element. This can be simply the size of the data in each element, but larger strides
@@ -186,7 +521,7 @@ pub struct VertexInputBindingDescription {
 }
 
 /// Describes a single vertex buffer attribute mapping.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct VertexInputAttributeDescription {
     /// The vertex buffer binding number that this attribute should take its data from.
     pub binding: u32,
@@ -206,6 +541,7 @@ pub struct VertexInputAttributeDescription {
 
 /// How the vertex source should be unrolled.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VertexInputRate {
     /// Each element of the source corresponds to a vertex.
     Vertex,
@@ -233,3 +569,216 @@ impl From<VertexInputRate> for ash::vk::VertexInputRate {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BindingCollisionError, RuntimeVertexBuilder, VertexAttribute,
+        VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputLimitError,
+        VertexInputRate, VertexInputState,
+    };
+    use crate::{device::Properties, format::Format};
+
+    fn state(binding: u32, location: u32, input_rate: VertexInputRate) -> VertexInputState {
+        VertexInputState::new()
+            .binding(
+                binding,
+                VertexInputBindingDescription {
+                    stride: 16,
+                    input_rate,
+                },
+            )
+            .attribute(
+                location,
+                VertexInputAttributeDescription {
+                    binding,
+                    format: Format::R32G32B32A32_SFLOAT,
+                    offset: 0,
+                },
+            )
+    }
+
+    #[test]
+    fn merge() {
+        let per_vertex = state(0, 0, VertexInputRate::Vertex);
+        let per_instance = state(0, 1, VertexInputRate::Instance { divisor: 2 });
+
+        let merged = per_vertex.clone().merge(per_instance).unwrap();
+        assert_eq!(merged.bindings.len(), 2);
+        assert_eq!(merged.bindings[&0].input_rate, VertexInputRate::Vertex);
+        assert_eq!(
+            merged.bindings[&1].input_rate,
+            VertexInputRate::Instance { divisor: 2 },
+        );
+        assert_eq!(merged.attributes[&0].binding, 0);
+        assert_eq!(merged.attributes[&1].binding, 1);
+
+        let merged = per_vertex
+            .clone()
+            .merge(state(3, 1, VertexInputRate::Vertex))
+            .unwrap();
+        assert_eq!(merged.attributes[&1].binding, 3);
+
+        assert_eq!(
+            per_vertex
+                .merge(state(1, 0, VertexInputRate::Vertex))
+                .unwrap_err(),
+            BindingCollisionError { location: 0 },
+        );
+    }
+
+    #[test]
+    fn validate_against_limits() {
+        let properties = Properties {
+            max_vertex_input_bindings: 2,
+            max_vertex_input_attributes: 4,
+            max_vertex_input_binding_stride: 32,
+            max_vertex_input_attribute_offset: 8,
+            ..Default::default()
+        };
+
+        let valid = state(1, 3, VertexInputRate::Vertex);
+        assert_eq!(valid.validate_against_limits(&properties), Ok(()));
+
+        assert_eq!(
+            state(3, 0, VertexInputRate::Vertex).validate_against_limits(&properties),
+            Err(VertexInputLimitError::MaxVertexInputBindingsExceeded {
+                max: 2,
+                obtained: 4,
+            }),
+        );
+        assert_eq!(
+            state(0, 5, VertexInputRate::Vertex).validate_against_limits(&properties),
+            Err(VertexInputLimitError::MaxVertexInputAttributesExceeded {
+                max: 4,
+                obtained: 6,
+            }),
+        );
+
+        let mut state = valid;
+        state.bindings.get_mut(&1).unwrap().stride = 48;
+        let err = state.validate_against_limits(&properties).unwrap_err();
+        assert_eq!(
+            err,
+            VertexInputLimitError::MaxVertexInputBindingStrideExceeded {
+                binding: 1,
+                max: 32,
+                obtained: 48,
+            },
+        );
+        assert_eq!(err.excess(), 16);
+
+        state.bindings.get_mut(&1).unwrap().stride = 16;
+        state.attributes.get_mut(&3).unwrap().offset = 12;
+        assert_eq!(
+            state.validate_against_limits(&properties),
+            Err(
+                VertexInputLimitError::MaxVertexInputAttributeOffsetExceeded {
+                    location: 3,
+                    max: 8,
+                    obtained: 12,
+                }
+            ),
+        );
+    }
+
+    #[test]
+    fn insertion_order() {
+        let attribute = |offset| VertexInputAttributeDescription {
+            binding: 0,
+            format: Format::R32_SFLOAT,
+            offset,
+        };
+        let binding = VertexInputBindingDescription {
+            stride: 8,
+            input_rate: VertexInputRate::Vertex,
+        };
+
+        let in_order = VertexInputState::new()
+            .binding(0, binding.clone())
+            .attribute(0, attribute(0))
+            .attribute(1, attribute(4));
+        let out_of_order = VertexInputState::new()
+            .attribute(1, attribute(4))
+            .attribute(0, attribute(0))
+            .binding(0, binding);
+        assert_eq!(in_order, out_of_order);
+
+        assert_ne!(in_order, out_of_order.attribute(1, attribute(0)));
+    }
+
+    #[test]
+    fn layout_hash() {
+        let per_vertex = state(0, 0, VertexInputRate::Vertex);
+        let per_instance = state(1, 1, VertexInputRate::Instance { divisor: 1 });
+
+        let merged = per_vertex.clone().merge(per_instance.clone()).unwrap();
+        let merged_reversed = per_instance.merge(per_vertex.clone()).unwrap();
+        assert_eq!(merged.layout_hash(), merged_reversed.layout_hash());
+
+        assert_ne!(per_vertex.layout_hash(), merged.layout_hash());
+        assert_ne!(
+            per_vertex.layout_hash(),
+            state(0, 0, VertexInputRate::Instance { divisor: 1 }).layout_hash(),
+        );
+
+        let mut moved = per_vertex.clone();
+        moved.attributes.get_mut(&0).unwrap().offset = 4;
+        assert_ne!(per_vertex.layout_hash(), moved.layout_hash());
+    }
+
+    #[test]
+    fn layout_compatible() {
+        let positions = [[0.0f32; 3]; 2];
+        let normals = [[0.0f32; 3]; 3];
+        let state = |name, data: &[[f32; 3]]| {
+            RuntimeVertexBuilder::new()
+                .add(VertexAttribute::new(name, Format::R32G32B32_SFLOAT), data)
+                .into_vertex_input_state()
+                .unwrap()
+                .1
+        };
+
+        let a = state("position", &positions);
+        let b = state("normal", &normals);
+        assert!(a.layout_compatible(&b));
+        assert_eq!(a.layout_hash(), b.layout_hash());
+
+        let mut moved = b.clone();
+        moved.attributes.get_mut(&0).unwrap().offset = 4;
+        assert!(!a.layout_compatible(&moved));
+
+        let mut strided = b;
+        strided.bindings.get_mut(&0).unwrap().stride = 16;
+        assert!(!a.layout_compatible(&strided));
+    }
+
+    #[test]
+    fn to_attributes() {
+        let positions = [[0.0f32; 3]; 2];
+        let colors = [[0u8; 4]; 2];
+
+        let (_, state) = RuntimeVertexBuilder::new()
+            .add_at_location(
+                3,
+                VertexAttribute::new("color", Format::R8G8B8A8_UNORM),
+                &colors,
+            )
+            .add_at_location(
+                1,
+                VertexAttribute::new("position", Format::R32G32B32_SFLOAT),
+                &positions,
+            )
+            .into_vertex_input_state()
+            .unwrap();
+
+        assert_eq!(
+            state.to_attributes(),
+            [
+                VertexAttribute::new("location_1", Format::R32G32B32_SFLOAT).with_offset(4),
+                VertexAttribute::new("location_3", Format::R8G8B8A8_UNORM).with_offset(0),
+            ],
+        );
+        assert!(VertexInputState::new().to_attributes().is_empty());
+    }
+}
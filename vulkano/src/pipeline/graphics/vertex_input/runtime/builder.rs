@@ -0,0 +1,4142 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    deinterleave, error::RuntimeVertexBuildError, iter::RuntimeVertexIter, VertexAttribute,
+    VertexBufferInfo,
+};
+use crate::{
+    format::{Format, NumericType},
+    pipeline::graphics::vertex_input::{
+        VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate,
+        VertexInputState, VertexMemberInfo,
+    },
+    sampler::{ComponentMapping, ComponentSwizzle},
+    DeviceSize,
+};
+use ahash::HashMap;
+use bytemuck::Pod;
+use std::{borrow::Cow, fmt::Debug, io::Read, mem, ops::Range};
+
+/// Builds interleaved vertex data from separate slices for each attribute.
+///
+/// See the [module-level documentation](super) for an example.
+///
+/// # Input rates
+///
+/// Attributes added with [`add`](Self::add) advance per vertex, while attributes added with
+/// [`add_instanced`](Self::add_instanced) advance per instance. Attributes with different input
+/// rates can't share a vertex buffer, so [`build`](Self::build) only accepts attributes that all
+/// have the same input rate. If a builder mixes input rates, use
+/// [`build_per_input_rate`](Self::build_per_input_rate) instead, which produces a separate vertex
+/// buffer for each input rate.
+///
+/// # Formats
+///
+/// If an attribute is added with a format that isn't a
+/// [vertex attribute format](Format::is_vertex_attribute_format), the attribute is not added,
+/// and building returns an error instead. The same happens if the elements of the data of an
+/// attribute have a size of zero, such as a slice of a unit struct.
+#[derive(Clone, Debug, Default)]
+pub struct RuntimeVertexBuilder<'d> {
+    pub(super) members: Vec<RuntimeVertexMember<'d>>,
+    // The stride requested with `with_stride`, which overrides the computed stride.
+    stride: Option<usize>,
+    // The error for the first attribute that couldn't be added because of its format or size.
+    add_error: Option<RuntimeVertexBuildError>,
+    // The byte order of the components in the built data.
+    endianness: Endianness,
+    // How the offsets of attributes with integer formats are aligned.
+    integer_alignment: IntegerAlignment,
+}
+
+/// A [`RuntimeVertexBuilder`] that doesn't borrow any data, because all of its attributes were
+/// added with [`add_owned`](RuntimeVertexBuilder::add_owned).
+pub type OwnedRuntimeVertexBuilder = RuntimeVertexBuilder<'static>;
+
+#[derive(Clone, Debug)]
+pub(super) struct RuntimeVertexMember<'d> {
+    pub(super) name: String,
+    pub(super) info: VertexMemberInfo,
+    // The data of the member, in the order that it was added with `add` and `extend`.
+    data: Vec<Cow<'d, [u8]>>,
+    // The number of bytes from the start of one element of `data` to the next. This is the size
+    // of the member given by `info`, unless it was added with `add_strided`, in which case the
+    // bytes after the member in each element are skipped.
+    source_stride: usize,
+    input_rate: VertexInputRate,
+    // The alignment that the offset of the member must satisfy within a vertex.
+    alignment: usize,
+    // The shader location that was assigned with `add_at_location`.
+    location: Option<u32>,
+    // Additional shader locations that read the same data, assigned with `add_aliased`.
+    aliases: Vec<u32>,
+    // The number of vertices of an attribute added with `add_constant` or `add_repeating`, whose
+    // data is repeated from the start until it provides data for that many vertices.
+    repeat_count: Option<usize>,
+    // The vertex buffer binding that was assigned with `add_to_binding`, 0 by default.
+    binding: u32,
+}
+
+impl<'d> RuntimeVertexMember<'d> {
+    // The number of elements in `data`.
+    #[inline]
+    fn count(&self) -> usize {
+        self.repeat_count.unwrap_or_else(|| {
+            self.data.iter().map(|data| data.len()).sum::<usize>() / self.source_stride
+        })
+    }
+
+    // Turns the data of a repeating member into the repeated data stored for every vertex, so
+    // that more data can be added after it.
+    fn expand_repeating(&mut self) {
+        if let Some(count) = self.repeat_count.take() {
+            let data = self.data.concat();
+            let len = count * self.source_stride;
+            self.data = vec![Cow::Owned(data.into_iter().cycle().take(len).collect())];
+        }
+    }
+
+    // Takes the data of the member as a single slice with the components in the given byte
+    // order, which is only copied if there are multiple parts or the bytes must be swapped.
+    fn take_bytes(&mut self, endianness: Endianness) -> Cow<'d, [u8]> {
+        let mut parts = mem::take(&mut self.data);
+        let mut bytes = if parts.len() == 1 {
+            parts.pop().unwrap()
+        } else {
+            Cow::Owned(parts.concat())
+        };
+
+        let component_size = self.info.format.required_alignment() as usize;
+        let member_size = self.info.byte_size() as usize;
+
+        if endianness.is_swapped() && component_size > 1 {
+            // The bytes between the elements of strided data are left alone.
+            for element in bytes.to_mut().chunks_exact_mut(self.source_stride) {
+                for component in element[..member_size].chunks_exact_mut(component_size) {
+                    component.reverse();
+                }
+            }
+        }
+
+        bytes
+    }
+
+    // Adds data that holds the elements of the member without any bytes between them, spreading
+    // it out if the member is strided.
+    fn push_packed(&mut self, bytes: Vec<u8>) {
+        let member_size = self.info.byte_size() as usize;
+
+        if self.source_stride == member_size {
+            self.data.push(Cow::Owned(bytes));
+        } else {
+            let mut strided = vec![0; bytes.len() / member_size * self.source_stride];
+
+            for (src, dst) in bytes
+                .chunks_exact(member_size)
+                .zip(strided.chunks_exact_mut(self.source_stride))
+            {
+                dst[..member_size].copy_from_slice(src);
+            }
+
+            self.data.push(Cow::Owned(strided));
+        }
+    }
+
+    // The bytes that the member occupies within a vertex.
+    #[inline]
+    pub(super) fn byte_range(&self) -> Range<usize> {
+        self.info.offset..self.info.offset + self.info.byte_size() as usize
+    }
+}
+
+impl<'d> RuntimeVertexBuilder<'d> {
+    /// Creates a new builder without any attributes.
+    #[inline]
+    pub fn new() -> Self {
+        RuntimeVertexBuilder {
+            members: Vec::new(),
+            stride: None,
+            add_error: None,
+            endianness: Endianness::Native,
+            integer_alignment: IntegerAlignment::Ignore,
+        }
+    }
+
+    /// Creates a new builder without any attributes, with space reserved for `attributes`
+    /// attributes.
+    #[inline]
+    pub fn with_capacity(attributes: usize) -> Self {
+        RuntimeVertexBuilder {
+            members: Vec::with_capacity(attributes),
+            stride: None,
+            add_error: None,
+            endianness: Endianness::Native,
+            integer_alignment: IntegerAlignment::Ignore,
+        }
+    }
+
+    /// Sets the stride of a vertex, overriding the stride that is computed from the attributes.
+    ///
+    /// The bytes between the end of the last attribute and the end of a vertex are filled with
+    /// zeroes. If attributes with different input rates are added, the stride applies to the
+    /// vertex buffer of each input rate. Building returns an error if the stride is smaller than
+    /// the bytes that the attributes of a vertex occupy.
+    #[inline]
+    pub fn with_stride(mut self, stride: u32) -> Self {
+        self.stride = Some(stride as usize);
+        self
+    }
+
+    /// Sets the byte order of the components in the built data. The default is
+    /// [`Endianness::Native`].
+    ///
+    /// Each component of a format is swapped separately, as are the elements of packed formats.
+    /// If the byte order differs from the host's, the data of every attribute is copied when
+    /// building.
+    #[inline]
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Sets how the offsets of attributes with `UINT` and `SINT` formats are aligned to the size
+    /// of their components. The default is [`IntegerAlignment::Ignore`].
+    ///
+    /// Some drivers can't read integer attributes at offsets that aren't a multiple of their
+    /// component size. [`IntegerAlignment::Pad`] only affects attributes that are added after
+    /// this is called.
+    #[inline]
+    pub fn with_integer_alignment(mut self, integer_alignment: IntegerAlignment) -> Self {
+        self.integer_alignment = integer_alignment;
+        self
+    }
+
+    /// Adds an attribute whose data for each vertex is an element of `data`.
+    ///
+    /// The attribute is placed directly after the previously added attribute with the same input
+    /// rate.
+    ///
+    /// If `attribute.num_elements` is `None`, the number of elements is the size of `T` divided
+    /// by the block size of `attribute.format`. If the size of `T` is not a multiple of the block
+    /// size, the attribute is not added, and building returns
+    /// [`RuntimeVertexBuildError::StrideFormatMismatch`]. If `attribute.num_elements` is `Some`
+    /// and the size of `T` is not the block size multiplied by the number of elements, building
+    /// returns [`RuntimeVertexBuildError::FormatSizeMismatch`] instead.
+    ///
+    /// These checks only depend on `T` and `attribute`, so they are done once when the attribute
+    /// is added, and not for every vertex. [`add_unchecked`](Self::add_unchecked) skips them.
+    #[inline]
+    pub fn add<T: Pod>(self, attribute: VertexAttribute, data: &'d [T]) -> Self {
+        self.add_bytes(attribute, bytemuck::cast_slice(data), mem::size_of::<T>())
+    }
+
+    /// Adds an attribute like [`add`](Self::add), but without checking the size of `T` against
+    /// `attribute`.
+    ///
+    /// This is meant for attributes whose types and formats have already been validated, such
+    /// as in a mesh loading loop. The format itself is still checked, but the size checks of
+    /// `add` are only done in debug builds, where they panic instead of returning an error.
+    ///
+    /// The caller must uphold the invariant that `add` checks: `T` is not zero-sized, and its
+    /// size is the block size of `attribute.format` multiplied by `attribute.num_elements`, or a
+    /// multiple of the block size if `attribute.num_elements` is `None`. If it doesn't hold, the
+    /// layout doesn't describe the data, and building or reading the data may panic.
+    pub fn add_unchecked<T: Pod>(mut self, attribute: VertexAttribute, data: &'d [T]) -> Self {
+        let format_size = match self.check_format(&attribute) {
+            Some(format_size) => format_size,
+            None => return self,
+        };
+        let field_size = mem::size_of::<T>();
+        let num_elements = attribute
+            .num_elements
+            .unwrap_or((field_size / format_size) as u32);
+        debug_assert!(
+            field_size != 0 && field_size == format_size * num_elements as usize,
+            "size of attribute `{}` does not match its format",
+            attribute.name,
+        );
+
+        self.push_member(
+            0,
+            attribute,
+            Cow::Borrowed(bytemuck::cast_slice(data)),
+            field_size,
+            num_elements,
+            VertexInputRate::Vertex,
+            1,
+        )
+    }
+
+    /// Adds an attribute like [`add`](Self::add) if `data` is `Some`, and does nothing if it is
+    /// `None`.
+    ///
+    /// This allows a single chain of calls to build layouts where some attributes are optional.
+    /// Attributes that are added after a skipped attribute take its place, so the layout is the
+    /// same as if the skipped attribute had never been added.
+    #[inline]
+    pub fn add_optional<T: Pod>(self, attribute: VertexAttribute, data: Option<&'d [T]>) -> Self {
+        match data {
+            Some(data) => self.add(attribute, data),
+            None => self,
+        }
+    }
+
+    /// Adds an attribute whose data is `value` for each of `vertex_count` vertices.
+    ///
+    /// This can provide a default for an attribute that a shader requires, but that there's no
+    /// data for. The value is only stored once, and is repeated as the data is built.
+    pub fn add_constant<T: Pod>(
+        self,
+        attribute: VertexAttribute,
+        value: T,
+        vertex_count: usize,
+    ) -> Self {
+        let num_members = self.members.len();
+        let mut builder = self.add_member(
+            attribute,
+            bytemuck::bytes_of(&value).to_vec().into(),
+            mem::size_of::<T>(),
+            VertexInputRate::Vertex,
+            1,
+        );
+
+        // The attribute isn't added if its format or size is unsupported.
+        if builder.members.len() > num_members {
+            builder.members.last_mut().unwrap().repeat_count = Some(vertex_count);
+        }
+
+        builder
+    }
+
+    /// Adds an attribute whose data for the vertex with index `i` is the element of `data` at
+    /// index `i % data.len()`, for each of `vertex_count` vertices.
+    ///
+    /// This is useful for attributes with a repeating pattern, such as the texture coordinates of
+    /// the corners of many quads. The data isn't copied, and is repeated as the data is built.
+    ///
+    /// If `data` is empty, the attribute is not added, and building returns
+    /// [`RuntimeVertexBuildError::EmptySlice`].
+    pub fn add_repeating<T: Pod>(
+        mut self,
+        attribute: VertexAttribute,
+        data: &'d [T],
+        vertex_count: usize,
+    ) -> Self {
+        if data.is_empty() {
+            self.add_error
+                .get_or_insert(RuntimeVertexBuildError::EmptySlice {
+                    attribute: attribute.name.into_owned(),
+                });
+
+            return self;
+        }
+
+        let num_members = self.members.len();
+        let mut builder = self.add(attribute, data);
+
+        // The attribute isn't added if its format or size is unsupported.
+        if builder.members.len() > num_members {
+            builder.members.last_mut().unwrap().repeat_count = Some(vertex_count);
+        }
+
+        builder
+    }
+
+    /// Adds an attribute whose data is given as raw bytes, in elements of `stride` bytes for each
+    /// vertex.
+    ///
+    /// This is useful when the data has already been decoded into bytes, for example from a
+    /// glTF file, and there's no Rust type that represents an element.
+    ///
+    /// The elements are checked like those of [`add`](Self::add), with `stride` in place of the
+    /// size of `T`. If the length of `data` is not a multiple of `stride`, the attribute is not
+    /// added, and building returns [`RuntimeVertexBuildError::DataSizeMismatch`].
+    #[inline]
+    pub fn add_bytes(self, attribute: VertexAttribute, data: &'d [u8], stride: usize) -> Self {
+        self.add_member(attribute, data.into(), stride, VertexInputRate::Vertex, 1)
+    }
+
+    /// Adds an attribute whose data is given as raw bytes, with the element for each vertex
+    /// starting `stride` bytes after the previous one.
+    ///
+    /// Unlike [`add_bytes`](Self::add_bytes), `stride` can be greater than the size of an
+    /// element, which is the block size of `attribute.format` multiplied by
+    /// `attribute.num_elements`, or 1 if that is `None`. The bytes between the elements are
+    /// skipped when the data is interleaved, so an attribute can be read from sparse or
+    /// interleaved source data, such as a glTF buffer view with a `byteStride`, without copying
+    /// it first. If `data` ends right after its last element, it is copied once to pad it to a
+    /// whole number of strides.
+    ///
+    /// If `stride` is smaller than an element, or `data` doesn't end with a whole element, the
+    /// error [`RuntimeVertexBuildError::SourceStrideMismatch`] is returned when building.
+    pub fn add_strided(
+        mut self,
+        attribute: VertexAttribute,
+        data: &'d [u8],
+        stride: usize,
+    ) -> Self {
+        let num_elements = attribute.num_elements.unwrap_or(1);
+        let element_size =
+            attribute.format.block_size().unwrap_or(0) as usize * num_elements as usize;
+
+        // Formats without a block size are reported by `add_member`.
+        if element_size != 0
+            && (stride < element_size
+                || (data.len() % stride != 0 && data.len() % stride < element_size))
+        {
+            self.add_error
+                .get_or_insert(RuntimeVertexBuildError::SourceStrideMismatch {
+                    attribute: attribute.name.into_owned(),
+                    stride,
+                    element_size,
+                    data_len: data.len(),
+                });
+
+            return self;
+        }
+
+        let data: Cow<'d, [u8]> = if element_size != 0 && data.len() % stride != 0 {
+            let mut padded = data.to_vec();
+            padded.resize(align_up(data.len(), stride), 0);
+            padded.into()
+        } else {
+            data.into()
+        };
+
+        // The member is added without data, so that the size of its elements can be checked
+        // against its format as usual, and the strided data is added afterwards.
+        let num_members = self.members.len();
+        let mut builder = self.add_member(
+            VertexAttribute {
+                num_elements: Some(num_elements),
+                ..attribute
+            },
+            Cow::Borrowed(&[]),
+            element_size,
+            VertexInputRate::Vertex,
+            1,
+        );
+
+        if builder.members.len() > num_members {
+            let member = builder.members.last_mut().unwrap();
+            member.data = vec![data];
+            member.source_stride = stride;
+        }
+
+        builder
+    }
+
+    /// Adds an attribute whose data for each vertex is an element of `data`, taking ownership of
+    /// the data.
+    ///
+    /// Unlike [`add`](Self::add), the data doesn't need to outlive the builder. A builder whose
+    /// attributes are all added this way is an [`OwnedRuntimeVertexBuilder`], which produces an
+    /// [`OwnedRuntimeVertexIter`](super::OwnedRuntimeVertexIter) that can be returned from the
+    /// function that generated the data.
+    #[inline]
+    pub fn add_owned<T: Pod>(self, attribute: VertexAttribute, data: Vec<T>) -> Self {
+        let bytes = bytemuck::cast_slice(&data).to_vec();
+
+        self.add_member(
+            attribute,
+            bytes.into(),
+            mem::size_of::<T>(),
+            VertexInputRate::Vertex,
+            1,
+        )
+    }
+
+    /// Adds an attribute whose data for each vertex is an element of `data`, at `offset` bytes
+    /// from the start of the vertex.
+    ///
+    /// This is equivalent to calling [`add`](Self::add) with
+    /// [`attribute.with_offset(offset)`](VertexAttribute::with_offset). The attribute must not
+    /// overlap with other attributes, which is checked when building.
+    #[inline]
+    pub fn add_at<T: Pod>(self, attribute: VertexAttribute, data: &'d [T], offset: usize) -> Self {
+        self.add(attribute.with_offset(offset), data)
+    }
+
+    /// Adds an attribute whose data for each vertex is an element of `data`, aligned to the
+    /// size of the components of its format.
+    ///
+    /// If the end of the previously added attribute is not suitably aligned, padding bytes are
+    /// inserted before the attribute. The stride of the vertex is rounded up to the alignment as
+    /// well, so that the attribute is aligned in every vertex.
+    #[inline]
+    pub fn add_aligned<T: Pod>(self, attribute: VertexAttribute, data: &'d [T]) -> Self {
+        let alignment = attribute.format.required_alignment() as usize;
+
+        self.add_member(
+            attribute,
+            Cow::Borrowed(bytemuck::cast_slice(data)),
+            mem::size_of::<T>(),
+            VertexInputRate::Vertex,
+            alignment,
+        )
+    }
+
+    /// Adds an attribute whose data for each instance is an element of `data`.
+    ///
+    /// `divisor` is the number of consecutive instances that use the same element. Attributes
+    /// with different divisors are placed in different vertex buffers by
+    /// [`build_per_input_rate`](Self::build_per_input_rate). The divisor is part of the input
+    /// rate of the resulting [`VertexBufferInfo`], and of the binding descriptions created from
+    /// it.
+    ///
+    /// Per-instance attributes can't share a vertex buffer with per-vertex attributes, so if
+    /// attributes were also added with [`add`](Self::add), [`build`](Self::build) returns
+    /// [`RuntimeVertexBuildError::MixedInputRates`]. Such a builder is built with
+    /// [`build_per_input_rate`](Self::build_per_input_rate) instead, or the per-instance
+    /// attributes are added to their own binding with
+    /// [`add_instanced_to_binding`](Self::add_instanced_to_binding) and the builder is built with
+    /// [`build_multi`](Self::build_multi).
+    ///
+    /// A divisor other than 1 requires the [`vertex_attribute_instance_rate_divisor`] feature to
+    /// be enabled on the device, and a divisor of 0 additionally requires the
+    /// [`vertex_attribute_instance_rate_zero_divisor`] feature. The divisor must not exceed the
+    /// [`max_vertex_attrib_divisor`] limit. These are checked when creating the graphics
+    /// pipeline.
+    ///
+    /// [`vertex_attribute_instance_rate_divisor`]: crate::device::Features::vertex_attribute_instance_rate_divisor
+    /// [`vertex_attribute_instance_rate_zero_divisor`]: crate::device::Features::vertex_attribute_instance_rate_zero_divisor
+    /// [`max_vertex_attrib_divisor`]: crate::device::Properties::max_vertex_attrib_divisor
+    #[inline]
+    pub fn add_instanced<T: Pod>(
+        self,
+        attribute: VertexAttribute,
+        data: &'d [T],
+        divisor: u32,
+    ) -> Self {
+        self.add_member(
+            attribute,
+            Cow::Borrowed(bytemuck::cast_slice(data)),
+            mem::size_of::<T>(),
+            VertexInputRate::Instance { divisor },
+            1,
+        )
+    }
+
+    /// Adds an attribute like [`add`](Self::add), but to the vertex buffer with the given
+    /// binding number instead of binding 0.
+    ///
+    /// The attribute is placed after the previously added attribute with the same binding and
+    /// input rate, in the same way that [`add`](Self::add) places it in binding 0, and each
+    /// binding gets its own stride. If attributes are added to bindings other
+    /// than 0, the data must be built with [`build_multi`](Self::build_multi).
+    #[inline]
+    pub fn add_to_binding<T: Pod>(
+        self,
+        binding: u32,
+        attribute: VertexAttribute,
+        data: &'d [T],
+    ) -> Self {
+        self.add_member_to_binding(
+            binding,
+            attribute,
+            Cow::Borrowed(bytemuck::cast_slice(data)),
+            mem::size_of::<T>(),
+            VertexInputRate::Vertex,
+            1,
+        )
+    }
+
+    /// Adds an attribute like [`add_instanced`](Self::add_instanced), but to the vertex buffer
+    /// with the given binding number instead of binding 0.
+    ///
+    /// See [`add_to_binding`](Self::add_to_binding) for how the attribute is laid out.
+    #[inline]
+    pub fn add_instanced_to_binding<T: Pod>(
+        self,
+        binding: u32,
+        attribute: VertexAttribute,
+        data: &'d [T],
+        divisor: u32,
+    ) -> Self {
+        self.add_member_to_binding(
+            binding,
+            attribute,
+            Cow::Borrowed(bytemuck::cast_slice(data)),
+            mem::size_of::<T>(),
+            VertexInputRate::Instance { divisor },
+            1,
+        )
+    }
+
+    /// Adds a matrix attribute whose data for each vertex is an element of `data`, with `columns`
+    /// columns of format `column_format`.
+    ///
+    /// Each column is added as a separate member named `base_name[0]` to
+    /// `base_name[columns - 1]`, following the previous one, so that the columns occupy
+    /// consecutive locations like matrix inputs of a shader. A shader input called `base_name`
+    /// whose number of elements is `columns` reads all of them. For per-instance matrices, pass a
+    /// [`VertexAttribute::new_array`] to [`add_instanced`](Self::add_instanced) instead.
+    ///
+    /// Building returns [`RuntimeVertexBuildError::MatrixSizeMismatch`] if the size of `T` is not
+    /// the block size of `column_format` multiplied by `columns`.
+    pub fn add_matrix<T: Pod>(
+        mut self,
+        base_name: impl Into<Cow<'static, str>>,
+        column_format: Format,
+        columns: u32,
+        data: &'d [T],
+    ) -> Self {
+        let base_name = base_name.into();
+        let block_size = match column_format.block_size() {
+            Some(block_size) => block_size as usize,
+            // The format is reported like for any other attribute.
+            None => {
+                let attribute = VertexAttribute {
+                    name: base_name,
+                    format: column_format,
+                    num_elements: Some(columns),
+                    offset: None,
+                };
+
+                return self.add(attribute, data);
+            }
+        };
+        let matrix_size = block_size * columns as usize;
+
+        if mem::size_of::<T>() != matrix_size {
+            self.add_error
+                .get_or_insert(RuntimeVertexBuildError::MatrixSizeMismatch {
+                    attribute: base_name.into_owned(),
+                    matrix_size,
+                    element_size: mem::size_of::<T>(),
+                });
+
+            return self;
+        }
+
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+
+        for column in 0..columns as usize {
+            let range = column * block_size..(column + 1) * block_size;
+            let column_data: Vec<u8> = bytes
+                .chunks_exact(mem::size_of::<T>())
+                .flat_map(|matrix| &matrix[range.clone()])
+                .copied()
+                .collect();
+            let attribute = VertexAttribute {
+                name: format!("{}[{}]", base_name, column).into(),
+                format: column_format,
+                num_elements: None,
+                offset: None,
+            };
+
+            self = self.add_member(
+                attribute,
+                column_data.into(),
+                block_size,
+                VertexInputRate::Vertex,
+                1,
+            );
+        }
+
+        self
+    }
+
+    /// Adds the attributes of a vertex struct, where each element of `data` contains the data of
+    /// all `attributes` for one vertex.
+    ///
+    /// The offset of each attribute within `T` is its [`offset`](VertexAttribute::offset) if it
+    /// has one, and otherwise the end of the previous attribute. The attributes of `T` are placed
+    /// after the previously added attributes of a vertex, and the data of each attribute is
+    /// copied out of the structs. The attributes returned by
+    /// [`RuntimeVertex::attributes`](super::RuntimeVertex::attributes) can be used to describe a
+    /// type that implements [`RuntimeVertex`](super::RuntimeVertex).
+    ///
+    /// If an attribute extends past the end of `T`, building returns
+    /// [`RuntimeVertexBuildError::AttributeOutsideStruct`]. If none of the attributes have an
+    /// offset, their sizes must add up to the size of `T`, and otherwise building returns
+    /// [`RuntimeVertexBuildError::StructSizeMismatch`]. Attributes with explicit offsets may leave
+    /// padding between them, but must not overlap, which is checked when building like for any
+    /// other attributes.
+    pub fn add_struct<T: Pod>(mut self, attributes: &[VertexAttribute], data: &[T]) -> Self {
+        let struct_size = mem::size_of::<T>();
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        let base_offset = self.end_of(0, VertexInputRate::Vertex);
+
+        // The bytes of each attribute within `T`. Every attribute is checked before any of them
+        // is added.
+        let mut ranges = Vec::with_capacity(attributes.len());
+        let mut next_offset = 0;
+
+        for attribute in attributes {
+            // An unsupported format is reported when building, so its size doesn't matter.
+            let element_size = attribute.format.block_size().unwrap_or(0) as usize
+                * attribute.num_elements.unwrap_or(1) as usize;
+            let offset = attribute.offset.unwrap_or(next_offset);
+
+            if offset + element_size > struct_size {
+                self.add_error
+                    .get_or_insert(RuntimeVertexBuildError::AttributeOutsideStruct {
+                        attribute: attribute.name.clone().into_owned(),
+                        end: offset + element_size,
+                        struct_size,
+                    });
+
+                return self;
+            }
+
+            ranges.push(offset..offset + element_size);
+            next_offset = offset + element_size;
+        }
+
+        // Without explicit offsets, the attributes are packed, so they must fill `T` exactly.
+        let attributes_size: usize = ranges.iter().map(ExactSizeIterator::len).sum();
+
+        if attributes
+            .iter()
+            .all(|attribute| attribute.offset.is_none())
+            && attributes_size != struct_size
+        {
+            self.add_error
+                .get_or_insert(RuntimeVertexBuildError::StructSizeMismatch {
+                    attributes_size,
+                    struct_size,
+                });
+
+            return self;
+        }
+
+        for (attribute, range) in attributes.iter().zip(ranges) {
+            let member_data: Vec<u8> = bytes
+                .chunks_exact(struct_size)
+                .flat_map(|vertex| &vertex[range.clone()])
+                .copied()
+                .collect();
+
+            self = self.add_member(
+                attribute.clone().with_offset(base_offset + range.start),
+                member_data.into(),
+                range.len(),
+                VertexInputRate::Vertex,
+                1,
+            );
+        }
+
+        self
+    }
+
+    /// Adds an attribute with a 16-bit floating point format, whose data for each vertex is an
+    /// element of `data` converted to half precision.
+    ///
+    /// `N` must be the number of components of `attribute.format`, multiplied by the number of
+    /// elements of the attribute if it has one. The components are converted when the attribute
+    /// is added.
+    ///
+    /// If `attribute.format` is a vertex attribute format, but not one whose components are all
+    /// 16-bit floating point numbers, such as `R16G16_SFLOAT`, building returns
+    /// [`RuntimeVertexBuildError::NotHalfFloatFormat`]. If `N` is not the number of components of
+    /// the attribute, building returns [`RuntimeVertexBuildError::FormatSizeMismatch`].
+    pub fn add_f16<const N: usize>(
+        mut self,
+        attribute: VertexAttribute,
+        data: &[[f32; N]],
+    ) -> Self {
+        // Unsupported formats are reported when building.
+        if attribute.format.is_vertex_attribute_format() {
+            let format = attribute.format;
+            let is_half_float = format.numeric_type() == Some(NumericType::SFLOAT)
+                && format
+                    .components()
+                    .iter()
+                    .all(|&bits| bits == 0 || bits == 16);
+
+            if !is_half_float {
+                self.add_error
+                    .get_or_insert(RuntimeVertexBuildError::NotHalfFloatFormat {
+                        attribute: attribute.name.into_owned(),
+                        format,
+                    });
+
+                return self;
+            }
+
+            let num_components =
+                format.component_count() as usize * attribute.num_elements.unwrap_or(1) as usize;
+
+            if N != num_components {
+                self.add_error
+                    .get_or_insert(RuntimeVertexBuildError::FormatSizeMismatch {
+                        attribute: attribute.name.into_owned(),
+                        expected: num_components * mem::size_of::<half::f16>(),
+                        provided: N * mem::size_of::<half::f16>(),
+                    });
+
+                return self;
+            }
+        }
+
+        let bytes: Vec<u8> = data
+            .iter()
+            .flatten()
+            .flat_map(|&value| half::f16::from_f32(value).to_bits().to_ne_bytes())
+            .collect();
+
+        self.add_member(
+            attribute,
+            bytes.into(),
+            N * mem::size_of::<half::f16>(),
+            VertexInputRate::Vertex,
+            1,
+        )
+    }
+
+    /// Adds an attribute like [`add`](Self::add), but with the components of each element
+    /// reordered according to `swizzle`.
+    ///
+    /// Each component of an element is replaced by the component of the same element that
+    /// `swizzle` selects for it, or by zero or one. The components are identified by their
+    /// position in memory, so `r` is the first component of an element even if the format is
+    /// `B8G8R8A8_UNORM`, and only as many members of `swizzle` as the format has components are
+    /// used. For example, RGBA colors can be added to a vertex buffer that is read with a BGRA
+    /// format by selecting the components in the order `Blue`, `Green`, `Red`, `Alpha`.
+    ///
+    /// The components are reordered when the attribute is added, and `data` is left untouched.
+    ///
+    /// If `attribute.format` is a packed vertex attribute format, such as
+    /// `A2B10G10R10_UNORM_PACK32`, building returns
+    /// [`RuntimeVertexBuildError::PackedFormatSwizzle`]. If `swizzle` selects a component that
+    /// `attribute.format` doesn't have, building returns
+    /// [`RuntimeVertexBuildError::MissingSwizzleComponent`].
+    pub fn add_swizzled<T: Pod>(
+        mut self,
+        attribute: VertexAttribute,
+        data: &[T],
+        swizzle: ComponentMapping,
+    ) -> Self {
+        let mut bytes = bytemuck::cast_slice(data).to_vec();
+        let format = attribute.format;
+
+        // Unsupported formats are reported when building.
+        if format.is_vertex_attribute_format() {
+            if format.is_packed() {
+                self.add_error
+                    .get_or_insert(RuntimeVertexBuildError::PackedFormatSwizzle {
+                        attribute: attribute.name.into_owned(),
+                        format,
+                    });
+
+                return self;
+            }
+
+            let num_components = format.component_count() as usize;
+            let component_size = format.required_alignment() as usize;
+            let swizzles = [swizzle.r, swizzle.g, swizzle.b, swizzle.a];
+            let sources = swizzle.component_map();
+
+            if let Some(&component) = sources[..num_components]
+                .iter()
+                .flatten()
+                .find(|&&source| source >= num_components)
+            {
+                self.add_error
+                    .get_or_insert(RuntimeVertexBuildError::MissingSwizzleComponent {
+                        attribute: attribute.name.into_owned(),
+                        component,
+                        format,
+                    });
+
+                return self;
+            }
+
+            let one = component_one(format.numeric_type().unwrap(), component_size);
+            let block_size = format.block_size().unwrap() as usize;
+            let mut original = [0; 32];
+
+            for element in bytes.chunks_exact_mut(block_size) {
+                original[..block_size].copy_from_slice(element);
+
+                for (index, component) in element.chunks_exact_mut(component_size).enumerate() {
+                    match sources[index] {
+                        Some(source) => component.copy_from_slice(
+                            &original[source * component_size..][..component_size],
+                        ),
+                        None if swizzles[index] == ComponentSwizzle::One => {
+                            component.copy_from_slice(&one)
+                        }
+                        None => component.fill(0),
+                    }
+                }
+            }
+        }
+
+        self.add_member(
+            attribute,
+            bytes.into(),
+            mem::size_of::<T>(),
+            VertexInputRate::Vertex,
+            1,
+        )
+    }
+
+    /// Adds an attribute whose data for each vertex is the element of `data` at the position
+    /// given by the corresponding element of `indices`.
+    ///
+    /// This de-indexes attributes that are indexed separately, as in OBJ files. The number of
+    /// vertices that the attribute provides data for is the length of `indices`, so all
+    /// attributes that are added this way must have the same number of indices to be built. The
+    /// elements are copied when the attribute is added.
+    ///
+    /// If an element of `indices` is not less than the length of `data`, the attribute is not
+    /// added, and building returns [`RuntimeVertexBuildError::IndexOutOfBounds`].
+    pub fn add_indexed<T: Pod>(
+        mut self,
+        attribute: VertexAttribute,
+        data: &[T],
+        indices: &[u32],
+    ) -> Self {
+        if let Some(&index) = indices.iter().find(|&&index| index as usize >= data.len()) {
+            self.add_error
+                .get_or_insert(RuntimeVertexBuildError::IndexOutOfBounds {
+                    attribute: attribute.name.into_owned(),
+                    index,
+                    len: data.len(),
+                });
+
+            return self;
+        }
+
+        let element_size = mem::size_of::<T>();
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        let mut gathered = Vec::with_capacity(indices.len() * element_size);
+
+        for &index in indices {
+            let index = index as usize;
+            gathered.extend_from_slice(&bytes[index * element_size..(index + 1) * element_size]);
+        }
+
+        self.add_member(
+            attribute,
+            gathered.into(),
+            element_size,
+            VertexInputRate::Vertex,
+            1,
+        )
+    }
+
+    /// Adds an attribute whose data for each vertex is the field that `extract` returns for an
+    /// element of `data`.
+    ///
+    /// This allows a field of a nested struct to be used as an attribute without flattening the
+    /// data first. `T` doesn't need to be [`Pod`], only the extracted field does. The fields are
+    /// copied when the attribute is added.
+    pub fn add_field<T, F: Pod>(
+        self,
+        attribute: VertexAttribute,
+        data: &[T],
+        extract: impl Fn(&T) -> &F,
+    ) -> Self {
+        let field_size = mem::size_of::<F>();
+        let mut gathered = Vec::with_capacity(data.len() * field_size);
+
+        for element in data {
+            gathered.extend_from_slice(bytemuck::bytes_of(extract(element)));
+        }
+
+        self.add_member(
+            attribute,
+            gathered.into(),
+            field_size,
+            VertexInputRate::Vertex,
+            1,
+        )
+    }
+
+    /// Adds an attribute like [`add`](Self::add), and assigns it to the given shader location in
+    /// the state returned by [`into_vertex_input_state`](Self::into_vertex_input_state).
+    ///
+    /// Either all or none of the attributes must be assigned a location.
+    #[inline]
+    pub fn add_at_location<T: Pod>(
+        self,
+        location: u32,
+        attribute: VertexAttribute,
+        data: &'d [T],
+    ) -> Self {
+        let num_members = self.members.len();
+        let mut builder = self.add(attribute, data);
+
+        // Attributes with an unsupported format are not added.
+        if builder.members.len() > num_members {
+            builder.members.last_mut().unwrap().location = Some(location);
+        }
+
+        builder
+    }
+
+    /// Adds an attribute like [`add_at_location`](Self::add_at_location), and also assigns it to
+    /// every location in `aliases`.
+    ///
+    /// The data is only stored once in the vertex buffer. In the state returned by
+    /// [`into_vertex_input_state`](Self::into_vertex_input_state), the attribute descriptions at
+    /// `location` and at all of the aliases read from the same offset of the same binding.
+    pub fn add_aliased<T: Pod>(
+        self,
+        location: u32,
+        aliases: &[u32],
+        attribute: VertexAttribute,
+        data: &'d [T],
+    ) -> Self {
+        let num_members = self.members.len();
+        let mut builder = self.add_at_location(location, attribute, data);
+
+        if builder.members.len() > num_members {
+            builder.members.last_mut().unwrap().aliases = aliases.to_vec();
+        }
+
+        builder
+    }
+
+    /// Appends more data to the attribute with the given name, which must have been added
+    /// before.
+    ///
+    /// When building, the data of each attribute is concatenated in the order that it was added.
+    /// Returns an error if there is no attribute with the given name, or if the size of `T` is not
+    /// the size of an element of the attribute's data.
+    pub fn extend<T: Pod>(
+        &mut self,
+        attribute_name: &str,
+        data: &'d [T],
+    ) -> Result<(), RuntimeVertexBuildError> {
+        let member = self
+            .members
+            .iter_mut()
+            .find(|member| member.name == attribute_name)
+            .ok_or_else(|| RuntimeVertexBuildError::UnknownAttribute {
+                attribute: attribute_name.to_owned(),
+            })?;
+
+        if mem::size_of::<T>() != member.source_stride {
+            return Err(RuntimeVertexBuildError::ElementSizeMismatch {
+                attribute: attribute_name.to_owned(),
+                expected: member.source_stride,
+                provided: mem::size_of::<T>(),
+            });
+        }
+
+        member.expand_repeating();
+        member.data.push(Cow::Borrowed(bytemuck::cast_slice(data)));
+
+        Ok(())
+    }
+
+    /// Appends interleaved vertex data with the layout described by `info` to the attributes of
+    /// the builder, for example to merge the vertices of several meshes with the same layout.
+    ///
+    /// The attributes of the builder with the input rate of `info` must have the same names,
+    /// offsets, formats and numbers of elements as the members of `info`, and must have the same
+    /// stride. The data is split up and copied into the data of each attribute, so it must be in
+    /// the same byte order as the data that the attributes were added with.
+    pub fn append_interleaved(
+        &mut self,
+        data: &[u8],
+        info: &VertexBufferInfo,
+    ) -> Result<(), RuntimeVertexBuildError> {
+        let num_members = self
+            .members
+            .iter()
+            .filter(|member| member.input_rate == info.input_rate)
+            .count();
+
+        if num_members == 0 || self.stride_of(info.input_rate) != info.stride as usize {
+            return Err(RuntimeVertexBuildError::LayoutMismatch { attribute: None });
+        }
+
+        for member in &self.members {
+            if member.input_rate != info.input_rate {
+                continue;
+            }
+
+            if info.member(&member.name) != Some(&member.info) {
+                return Err(RuntimeVertexBuildError::LayoutMismatch {
+                    attribute: Some(member.name.clone()),
+                });
+            }
+        }
+
+        if let Some((name, _)) = info
+            .members
+            .iter()
+            .find(|(name, _)| !self.members.iter().any(|member| &member.name == name))
+        {
+            return Err(RuntimeVertexBuildError::LayoutMismatch {
+                attribute: Some(name.clone()),
+            });
+        }
+
+        let members_data = deinterleave(data, info)?;
+
+        for (name, bytes) in members_data {
+            let member = self
+                .members
+                .iter_mut()
+                .find(|member| member.name == name)
+                .unwrap();
+            member.expand_repeating();
+            member.push_packed(bytes);
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn add_member(
+        self,
+        attribute: VertexAttribute,
+        data: Cow<'d, [u8]>,
+        field_size: usize,
+        input_rate: VertexInputRate,
+        alignment: usize,
+    ) -> Self {
+        self.add_member_to_binding(0, attribute, data, field_size, input_rate, alignment)
+    }
+
+    fn add_member_to_binding(
+        mut self,
+        binding: u32,
+        attribute: VertexAttribute,
+        data: Cow<'d, [u8]>,
+        field_size: usize,
+        input_rate: VertexInputRate,
+        alignment: usize,
+    ) -> Self {
+        let format_size = match self.check_format(&attribute) {
+            Some(format_size) => format_size,
+            None => return self,
+        };
+
+        // There would be no way to tell how many vertices the data is for.
+        if field_size == 0 {
+            self.add_error
+                .get_or_insert(RuntimeVertexBuildError::ZeroSizedAttribute {
+                    attribute: attribute.name.into_owned(),
+                });
+
+            return self;
+        }
+
+        let num_elements = match attribute.num_elements {
+            Some(num_elements) if field_size != format_size * num_elements as usize => {
+                self.add_error
+                    .get_or_insert(RuntimeVertexBuildError::FormatSizeMismatch {
+                        attribute: attribute.name.into_owned(),
+                        expected: format_size * num_elements as usize,
+                        provided: field_size,
+                    });
+
+                return self;
+            }
+            Some(num_elements) => num_elements,
+            // The components of packed formats are stored in a single element, so the data must
+            // consist of exactly one element as well.
+            None if attribute.format.is_packed() => {
+                if field_size != format_size {
+                    self.add_error
+                        .get_or_insert(RuntimeVertexBuildError::FormatSizeMismatch {
+                            attribute: attribute.name.into_owned(),
+                            expected: format_size,
+                            provided: field_size,
+                        });
+
+                    return self;
+                }
+
+                1
+            }
+            None => {
+                // The size of an element according to the widths of the components, which must
+                // agree with the block size for the number of elements to be meaningful.
+                let component_bytes = attribute
+                    .format
+                    .components()
+                    .iter()
+                    .map(|&bits| bits as usize)
+                    .sum::<usize>()
+                    / 8;
+
+                if component_bytes != format_size || field_size % format_size != 0 {
+                    self.add_error
+                        .get_or_insert(RuntimeVertexBuildError::StrideFormatMismatch {
+                            attribute: attribute.name.into_owned(),
+                            expected: (field_size / format_size).max(1) * component_bytes,
+                            provided: field_size,
+                        });
+
+                    return self;
+                }
+
+                (field_size / format_size) as u32
+            }
+        };
+
+        if data.len() % field_size != 0 {
+            self.add_error
+                .get_or_insert(RuntimeVertexBuildError::DataSizeMismatch {
+                    attribute: attribute.name.into_owned(),
+                    data_len: data.len(),
+                    element_size: field_size,
+                });
+
+            return self;
+        }
+
+        self.push_member(
+            binding,
+            attribute,
+            data,
+            field_size,
+            num_elements,
+            input_rate,
+            alignment,
+        )
+    }
+
+    // Returns the size of the format of `attribute`, or records an error and returns `None` if
+    // the format can't be used for vertex attributes.
+    fn check_format(&mut self, attribute: &VertexAttribute) -> Option<usize> {
+        match attribute.format.block_size() {
+            Some(block_size) if attribute.format.is_vertex_attribute_format() => {
+                Some(block_size as usize)
+            }
+            block_size => {
+                let attribute_name = attribute.name.clone().into_owned();
+                let format = attribute.format;
+                let error = match block_size {
+                    Some(_) => RuntimeVertexBuildError::UnsupportedFormat {
+                        attribute: attribute_name,
+                        format,
+                    },
+                    None => RuntimeVertexBuildError::FormatHasNoBlockSize {
+                        attribute: attribute_name,
+                        format,
+                    },
+                };
+                self.add_error.get_or_insert(error);
+
+                None
+            }
+        }
+    }
+
+    // Adds a member to `binding` whose data has already been checked against its format.
+    fn push_member(
+        mut self,
+        binding: u32,
+        attribute: VertexAttribute,
+        data: Cow<'d, [u8]>,
+        field_size: usize,
+        num_elements: u32,
+        input_rate: VertexInputRate,
+        alignment: usize,
+    ) -> Self {
+        let alignment = if self.integer_alignment == IntegerAlignment::Pad
+            && is_integer_format(attribute.format)
+        {
+            alignment.max(attribute.format.required_alignment() as usize)
+        } else {
+            alignment
+        };
+        let offset = attribute
+            .offset
+            .unwrap_or_else(|| align_up(self.end_of(binding, input_rate), alignment));
+
+        self.members.push(RuntimeVertexMember {
+            name: attribute.name.into_owned(),
+            info: VertexMemberInfo {
+                offset,
+                format: attribute.format,
+                num_elements,
+            },
+            data: vec![data],
+            source_stride: field_size,
+            input_rate,
+            alignment,
+            location: None,
+            aliases: Vec::new(),
+            repeat_count: None,
+            binding,
+        });
+
+        self
+    }
+
+    // The end of the last member of `binding` with the given input rate. The members of each
+    // binding are laid out separately.
+    fn end_of(&self, binding: u32, input_rate: VertexInputRate) -> usize {
+        self.members
+            .iter()
+            .filter(|member| member.binding == binding && member.input_rate == input_rate)
+            .map(|member| member.byte_range().end)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // The stride of the vertex buffer that holds the members of binding 0 with the given input
+    // rate.
+    pub(super) fn stride_of(&self, input_rate: VertexInputRate) -> usize {
+        if let Some(stride) = self.stride {
+            return stride;
+        }
+
+        let alignment = self
+            .members
+            .iter()
+            .filter(|member| member.binding == 0 && member.input_rate == input_rate)
+            .map(|member| member.alignment)
+            .max()
+            .unwrap_or(1);
+
+        align_up(self.end_of(0, input_rate), alignment)
+    }
+
+    // The distinct input rates of the members, in the order that they were first added.
+    fn input_rates(&self) -> Vec<VertexInputRate> {
+        let mut input_rates = Vec::new();
+
+        for member in &self.members {
+            if !input_rates.contains(&member.input_rate) {
+                input_rates.push(member.input_rate);
+            }
+        }
+
+        input_rates
+    }
+
+    /// Returns the number of vertices that the interleaved per-vertex data will contain, or
+    /// `None` if no per-vertex attributes were added.
+    ///
+    /// This is the smallest number of vertices that any attribute added with [`add`](Self::add)
+    /// provides data for. Attributes added with [`add_instanced`](Self::add_instanced) advance
+    /// per instance, so they don't limit the number of vertices. Attributes added to other
+    /// bindings with [`add_to_binding`](Self::add_to_binding) are in other vertex buffers, and
+    /// are not counted either.
+    #[inline]
+    pub fn vertex_count(&self) -> Option<usize> {
+        self.members
+            .iter()
+            .filter(|member| member.binding == 0 && member.input_rate == VertexInputRate::Vertex)
+            .map(RuntimeVertexMember::count)
+            .min()
+    }
+
+    // The smallest number of elements that any attribute provides data for, whatever its input
+    // rate.
+    fn element_count(&self) -> Option<usize> {
+        self.members.iter().map(RuntimeVertexMember::count).min()
+    }
+
+    /// Returns the number of bytes of the interleaved per-vertex data, which is the stride of a
+    /// vertex multiplied by [`vertex_count`](Self::vertex_count).
+    ///
+    /// Per-instance attributes and attributes of other bindings end up in separate vertex
+    /// buffers, whose sizes are not included. Returns 0 if no per-vertex attributes were added.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.stride_of(VertexInputRate::Vertex) * self.vertex_count().unwrap_or(0)
+    }
+
+    /// Builds the interleaved vertex data, returning an iterator over its bytes and a description
+    /// of its layout.
+    ///
+    /// Every attribute must have the same input rate, and must provide data for the same number
+    /// of vertices or instances. A builder that mixes input rates is built with
+    /// [`build_per_input_rate`](Self::build_per_input_rate) instead.
+    pub fn build(
+        self,
+    ) -> Result<(RuntimeVertexIter<'d>, VertexBufferInfo), RuntimeVertexBuildError> {
+        self.validate_added()?;
+        self.validate_input_rate()?;
+        self.validate_unique_names()?;
+        self.validate_layout()?;
+        self.validate_counts()?;
+
+        Ok(self.build_unchecked())
+    }
+
+    /// Returns the layout of the data that [`build`](Self::build) would build, without building
+    /// the data.
+    ///
+    /// This allows creating a pipeline for the layout before the data is available. The same
+    /// checks as in `build` are done, except for the ones that depend on the data, so the
+    /// attributes don't have to provide data for the same number of vertices yet.
+    pub fn layout(&self) -> Result<VertexBufferInfo, RuntimeVertexBuildError> {
+        self.validate_added()?;
+        self.validate_input_rate()?;
+        self.validate_unique_names()?;
+        self.validate_layout()?;
+
+        let input_rate = self.members[0].input_rate;
+
+        Ok(VertexBufferInfo {
+            members: self
+                .members
+                .iter()
+                .map(|member| (member.name.clone(), member.info.clone()))
+                .collect(),
+            stride: self.stride_of(input_rate) as u32,
+            input_rate,
+        })
+    }
+
+    /// Interleaves vertices from an iterator of vertex structs, where each vertex contains the
+    /// data of all `attributes`.
+    ///
+    /// The attributes are laid out within `T` like [`add_struct`](Self::add_struct) lays them
+    /// out, and the stride is the size of `T`. Because the data is already interleaved, the bytes
+    /// of each vertex are written to the result as they are, without collecting the vertices
+    /// first. If `vertices` is empty, the data is empty but the layout is still returned.
+    ///
+    /// Returns an error under the same conditions as [`layout`](Self::layout), including
+    /// [`RuntimeVertexBuildError::AttributeOutsideStruct`] and
+    /// [`RuntimeVertexBuildError::StructSizeMismatch`] if the attributes don't fit `T` like
+    /// `add_struct` requires. The attributes are checked before any vertex is read.
+    pub fn from_vertices<T: Pod>(
+        attributes: &[VertexAttribute],
+        vertices: impl IntoIterator<Item = T>,
+    ) -> Result<(Vec<u8>, VertexBufferInfo), RuntimeVertexBuildError> {
+        let vertex_size = mem::size_of::<T>();
+        let info = RuntimeVertexBuilder::new()
+            .with_stride(vertex_size as u32)
+            .add_struct::<T>(attributes, &[])
+            .layout()?;
+
+        let vertices = vertices.into_iter();
+        let mut data = Vec::with_capacity(vertices.size_hint().0 * vertex_size);
+
+        for vertex in vertices {
+            data.extend_from_slice(bytemuck::bytes_of(&vertex));
+        }
+
+        Ok((data, info))
+    }
+
+    /// Builds a separate vertex buffer for each input rate that was used, returning an iterator
+    /// over the bytes of each buffer and a description of its layout.
+    ///
+    /// The buffers are ordered by the first attribute that was added with each input rate.
+    /// Within each buffer, every attribute must provide data for the same number of vertices or
+    /// instances, but the number of vertices and the number of instances may differ.
+    pub fn build_per_input_rate(
+        self,
+    ) -> Result<Vec<(RuntimeVertexIter<'d>, VertexBufferInfo)>, RuntimeVertexBuildError> {
+        self.validate_added()?;
+
+        if self.members.is_empty() {
+            return Err(RuntimeVertexBuildError::NoAttributes);
+        }
+
+        self.validate_unique_names()?;
+        self.validate_layout()?;
+
+        let mut groups: Vec<RuntimeVertexBuilder<'d>> = self
+            .input_rates()
+            .into_iter()
+            .map(|_| RuntimeVertexBuilder {
+                members: Vec::new(),
+                stride: self.stride,
+                add_error: None,
+                endianness: self.endianness,
+                integer_alignment: self.integer_alignment,
+            })
+            .collect();
+        let input_rates = self.input_rates();
+
+        for member in self.members {
+            let index = input_rates
+                .iter()
+                .position(|&rate| rate == member.input_rate)
+                .unwrap();
+            groups[index].members.push(member);
+        }
+
+        groups
+            .into_iter()
+            .map(|group| {
+                group.validate_counts()?;
+                Ok(group.build_unchecked())
+            })
+            .collect()
+    }
+
+    /// Builds a separate, tightly packed vertex buffer for each attribute instead of interleaving
+    /// them, returning the bytes of each buffer and a description of its layout.
+    ///
+    /// The buffers are in the order that the attributes were added. The stride of each buffer is
+    /// the size of an element of the attribute's data, the attribute is at offset 0, and the
+    /// input rate is the one that the attribute was added with. Each buffer contains all of the
+    /// data of its attribute, so the buffers may provide data for different numbers of vertices.
+    pub fn build_separate(
+        self,
+    ) -> Result<Vec<(Vec<u8>, VertexBufferInfo)>, RuntimeVertexBuildError> {
+        self.validate_added()?;
+
+        Ok(self
+            .members
+            .into_iter()
+            .map(|mut member| {
+                member.expand_repeating();
+                let element_size = member.info.byte_size() as usize;
+                let bytes = member.take_bytes(self.endianness);
+                // The bytes between the elements of strided data are left out of the buffer.
+                let bytes = if member.source_stride == element_size {
+                    bytes.into_owned()
+                } else {
+                    bytes
+                        .chunks_exact(member.source_stride)
+                        .flat_map(|element| &element[..element_size])
+                        .copied()
+                        .collect()
+                };
+                let info = VertexBufferInfo {
+                    members: vec![(
+                        member.name,
+                        VertexMemberInfo {
+                            offset: 0,
+                            ..member.info
+                        },
+                    )],
+                    stride: element_size as u32,
+                    input_rate: member.input_rate,
+                };
+
+                (bytes, info)
+            })
+            .collect())
+    }
+
+    /// Builds the interleaved vertex data like [`build`](Self::build), split up into chunks of at
+    /// most `max_bytes_per_chunk` bytes each, for example to stay below the maximum size of a
+    /// buffer.
+    ///
+    /// The chunks are split at vertex boundaries, so every chunk contains whole vertices and has
+    /// the same layout. Each chunk is returned with the range of vertices that it contains. All
+    /// chunks except the last one contain the largest number of vertices that fits in
+    /// `max_bytes_per_chunk`. If there are no vertices, no chunks are returned.
+    ///
+    /// Returns [`RuntimeVertexBuildError::ChunkTooSmall`] if `max_bytes_per_chunk` is smaller
+    /// than the stride of a vertex.
+    pub fn build_chunked(
+        self,
+        max_bytes_per_chunk: usize,
+    ) -> Result<Vec<(Vec<u8>, VertexBufferInfo, Range<u32>)>, RuntimeVertexBuildError> {
+        let (mut iter, info) = self.build()?;
+        let stride = info.stride as usize;
+
+        if max_bytes_per_chunk < stride {
+            return Err(RuntimeVertexBuildError::ChunkTooSmall {
+                max_bytes_per_chunk,
+                stride,
+            });
+        }
+
+        let vertices_per_chunk = max_bytes_per_chunk / stride;
+        let vertex_count = iter.len() / stride;
+        let mut chunks = Vec::new();
+        let mut first_vertex = 0;
+
+        while first_vertex < vertex_count {
+            let chunk_vertices = vertices_per_chunk.min(vertex_count - first_vertex);
+            let mut bytes = vec![0; chunk_vertices * stride];
+            iter.read_exact(&mut bytes).unwrap();
+
+            let vertices = first_vertex as u32..(first_vertex + chunk_vertices) as u32;
+            chunks.push((bytes, info.clone(), vertices));
+            first_vertex += chunk_vertices;
+        }
+
+        Ok(chunks)
+    }
+
+    // Checks that there are attributes and that they all have the same input rate.
+    fn validate_input_rate(&self) -> Result<(), RuntimeVertexBuildError> {
+        let first = self
+            .members
+            .first()
+            .ok_or(RuntimeVertexBuildError::NoAttributes)?;
+
+        if self
+            .members
+            .iter()
+            .any(|member| member.input_rate != first.input_rate)
+        {
+            return Err(RuntimeVertexBuildError::MixedInputRates);
+        }
+
+        Ok(())
+    }
+
+    // Checks that no attribute has empty data.
+    fn validate_not_empty(&self) -> Result<(), RuntimeVertexBuildError> {
+        if let Some(member) = self.members.iter().find(|member| member.count() == 0) {
+            return Err(RuntimeVertexBuildError::EmptySlice {
+                attribute: member.name.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Checks that every attribute was added with a supported format and a non-zero size.
+    pub(super) fn validate_added(&self) -> Result<(), RuntimeVertexBuildError> {
+        match &self.add_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    // Checks that no two attributes have the same name.
+    pub(super) fn validate_unique_names(&self) -> Result<(), RuntimeVertexBuildError> {
+        for (index, member) in self.members.iter().enumerate() {
+            if self.members[..index]
+                .iter()
+                .any(|other| other.name == member.name)
+            {
+                return Err(RuntimeVertexBuildError::DuplicateAttribute {
+                    attribute: member.name.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks that the attributes of a vertex don't overlap, and that they fit in the requested
+    // stride. Offsets that were assigned by the builder always satisfy this, but offsets that
+    // were given explicitly might not.
+    pub(super) fn validate_layout(&self) -> Result<(), RuntimeVertexBuildError> {
+        if let Some(member) = self.members.iter().find(|member| member.binding != 0) {
+            return Err(RuntimeVertexBuildError::MultipleBindings {
+                binding: member.binding,
+            });
+        }
+
+        self.validate_overlap()?;
+        self.validate_stride()?;
+        self.validate_integer_alignment()
+    }
+
+    // Checks that attributes with integer formats are aligned to their component size, unless
+    // the check is disabled.
+    fn validate_integer_alignment(&self) -> Result<(), RuntimeVertexBuildError> {
+        if self.integer_alignment == IntegerAlignment::Ignore {
+            return Ok(());
+        }
+
+        for member in &self.members {
+            let required = member.info.format.required_alignment() as usize;
+
+            if is_integer_format(member.info.format) && member.info.offset % required != 0 {
+                return Err(RuntimeVertexBuildError::MisalignedAttribute {
+                    attribute: member.name.clone(),
+                    offset: member.info.offset,
+                    required,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks that no two attributes with the same input rate overlap.
+    fn validate_overlap(&self) -> Result<(), RuntimeVertexBuildError> {
+        for (index, first) in self.members.iter().enumerate() {
+            let first_range = first.byte_range();
+
+            if let Some(second) = self.members[index + 1..].iter().find(|second| {
+                let second_range = second.byte_range();
+
+                second.input_rate == first.input_rate
+                    && second_range.start < first_range.end
+                    && first_range.start < second_range.end
+            }) {
+                return Err(RuntimeVertexBuildError::OverlappingAttributes {
+                    first: first.name.clone(),
+                    second: second.name.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks that the requested stride, if any, is large enough to hold the members of every input
+    // rate.
+    fn validate_stride(&self) -> Result<(), RuntimeVertexBuildError> {
+        if let Some(stride) = self.stride {
+            let last_member = self
+                .members
+                .iter()
+                .max_by_key(|member| member.byte_range().end);
+
+            match last_member {
+                Some(member) if member.byte_range().end > stride => {
+                    return Err(RuntimeVertexBuildError::StrideTooSmall {
+                        attribute: member.name.clone(),
+                        stride: stride as u32,
+                        required: member.byte_range().end as u32,
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks that every attribute provides data for the same, nonzero number of elements.
+    fn validate_counts(&self) -> Result<(), RuntimeVertexBuildError> {
+        self.validate_not_empty()?;
+
+        let counts: Vec<(String, usize)> = self
+            .members
+            .iter()
+            .map(|member| (member.name.clone(), member.count()))
+            .collect();
+
+        if counts.iter().any(|&(_, count)| count != counts[0].1) {
+            return Err(RuntimeVertexBuildError::MismatchedVertexCounts { counts });
+        }
+
+        Ok(())
+    }
+
+    /// Builds the interleaved vertex data like [`build`](Self::build), and returns a
+    /// `VertexInputState` that describes it instead of a `VertexBufferInfo`.
+    ///
+    /// The data is read from binding 0. Attributes that were added with
+    /// [`add_at_location`](Self::add_at_location) are assigned to the given location. Otherwise,
+    /// the attributes are assigned to consecutive locations starting at 0, in the order that they
+    /// were added. Array attributes take up a location for each element, and 64-bit formats with
+    /// more than two components take up two locations.
+    ///
+    /// Returns an error if only some of the attributes were assigned a location, or if the
+    /// locations of two attributes overlap.
+    #[inline]
+    pub fn into_vertex_input_state(
+        self,
+    ) -> Result<(RuntimeVertexIter<'d>, VertexInputState), RuntimeVertexBuildError> {
+        self.into_vertex_input_state_at(0)
+    }
+
+    /// Same as [`into_vertex_input_state`](Self::into_vertex_input_state), but attributes
+    /// without an assigned location start at `first_location` instead of 0.
+    pub fn into_vertex_input_state_at(
+        self,
+        first_location: u32,
+    ) -> Result<(RuntimeVertexIter<'d>, VertexInputState), RuntimeVertexBuildError> {
+        let (iter, _, state) = self.build_with_state(0, first_location)?;
+
+        Ok((iter, state))
+    }
+
+    /// Builds a separate vertex buffer for each binding that attributes were added to with
+    /// [`add_to_binding`](Self::add_to_binding), and a `VertexInputState` that reads all of the
+    /// buffers.
+    ///
+    /// Attributes that were added without a binding are in binding 0. The buffers are returned
+    /// with their binding numbers, sorted by binding. Each buffer is built like
+    /// [`build`](Self::build) builds it, so the attributes of a binding must all have the same
+    /// input rate and provide data for the same number of vertices or instances.
+    ///
+    /// The locations of the attributes are assigned like
+    /// [`into_vertex_input_state`](Self::into_vertex_input_state) assigns them, continuing from
+    /// one binding to the next. Returns an error if the locations of two attributes overlap,
+    /// including attributes of different bindings.
+    pub fn build_multi(
+        self,
+    ) -> Result<
+        (
+            Vec<(u32, RuntimeVertexIter<'d>, VertexBufferInfo)>,
+            VertexInputState,
+        ),
+        RuntimeVertexBuildError,
+    > {
+        self.validate_added()?;
+
+        if self.members.is_empty() {
+            return Err(RuntimeVertexBuildError::NoAttributes);
+        }
+
+        self.validate_unique_names()?;
+
+        let mut bindings: Vec<u32> = self.members.iter().map(|member| member.binding).collect();
+        bindings.sort_unstable();
+        bindings.dedup();
+
+        let mut groups: Vec<RuntimeVertexBuilder<'d>> = bindings
+            .iter()
+            .map(|_| RuntimeVertexBuilder {
+                members: Vec::new(),
+                stride: self.stride,
+                add_error: None,
+                endianness: self.endianness,
+                integer_alignment: self.integer_alignment,
+            })
+            .collect();
+
+        for mut member in self.members {
+            let index = bindings.binary_search(&member.binding).unwrap();
+            // Within its own buffer, the member is laid out like a member of binding 0.
+            member.binding = 0;
+            groups[index].members.push(member);
+        }
+
+        let mut buffers = Vec::with_capacity(groups.len());
+        let mut combined_state = VertexInputState::new();
+        let mut next_location = 0;
+
+        for (binding, group) in bindings.into_iter().zip(groups) {
+            let (iter, info, state) = group.build_with_state(binding, next_location)?;
+            next_location = state
+                .attributes
+                .iter()
+                .map(|(&location, description)| location + format_locations(description.format))
+                .max()
+                .unwrap_or(next_location)
+                .max(next_location);
+            combined_state = combined_state.merge(state).map_err(|err| {
+                RuntimeVertexBuildError::LocationCollision {
+                    location: err.location,
+                }
+            })?;
+            buffers.push((binding, iter, info));
+        }
+
+        Ok((buffers, combined_state))
+    }
+
+    /// Returns the first location of each attribute, by name, as assigned by
+    /// [`into_vertex_input_state`](Self::into_vertex_input_state) or
+    /// [`build_multi`](Self::build_multi) if attributes were added to several bindings.
+    ///
+    /// This doesn't check whether the locations can actually be assigned. If two attributes have
+    /// the same name, only the location of the last one is returned.
+    pub fn location_map(&self) -> HashMap<String, u32> {
+        let mut bindings: Vec<u32> = self.members.iter().map(|member| member.binding).collect();
+        bindings.sort_unstable();
+        bindings.dedup();
+
+        let mut locations = HashMap::default();
+        let mut next_location = 0;
+
+        for binding in bindings {
+            // Like `build_multi`, the next binding continues after the highest location of this
+            // one.
+            let mut binding_location = next_location;
+
+            for member in self
+                .members
+                .iter()
+                .filter(|member| member.binding == binding)
+            {
+                let location = member.location.unwrap_or(binding_location);
+                binding_location =
+                    location + member.info.num_elements * format_locations(member.info.format);
+                next_location = next_location.max(binding_location);
+                locations.insert(member.name.clone(), location);
+            }
+        }
+
+        locations
+    }
+
+    // Builds the data like `into_vertex_input_state_at` does, with the state reading the data from
+    // `binding`.
+    fn build_with_state(
+        self,
+        binding: u32,
+        first_location: u32,
+    ) -> Result<(RuntimeVertexIter<'d>, VertexBufferInfo, VertexInputState), RuntimeVertexBuildError>
+    {
+        let locations: Vec<Option<u32>> =
+            self.members.iter().map(|member| member.location).collect();
+
+        if locations.iter().any(Option::is_some) && locations.iter().any(Option::is_none) {
+            return Err(RuntimeVertexBuildError::MixedLocationAssignment);
+        }
+
+        // For every aliased member, its first location, the number of locations it takes up,
+        // and the locations that alias it.
+        let aliases: Vec<(u32, u32, Vec<u32>)> = self
+            .members
+            .iter()
+            .filter(|member| !member.aliases.is_empty())
+            .map(|member| {
+                let num_locations = member.info.num_elements * format_locations(member.info.format);
+
+                (
+                    member.location.unwrap(),
+                    num_locations,
+                    member.aliases.clone(),
+                )
+            })
+            .collect();
+
+        let (iter, info) = self.build()?;
+        let mut state = vertex_input_state(&info, binding, locations, first_location)?;
+
+        for (location, num_locations, aliases) in aliases {
+            for alias in aliases {
+                for offset in 0..num_locations {
+                    let description = match state.attributes.get(&(location + offset)) {
+                        Some(&description) => description,
+                        // The second location of a 64-bit format.
+                        None => continue,
+                    };
+                    let alias_location = alias + offset;
+
+                    for occupied in
+                        alias_location..alias_location + format_locations(description.format)
+                    {
+                        if state.attributes.iter().any(|(&other, other_description)| {
+                            (other..other + format_locations(other_description.format))
+                                .contains(&occupied)
+                        }) {
+                            return Err(RuntimeVertexBuildError::LocationCollision {
+                                location: occupied,
+                            });
+                        }
+                    }
+
+                    state.attributes.insert(alias_location, description);
+                }
+            }
+        }
+
+        Ok((iter, info, state))
+    }
+
+    /// Builds the interleaved vertex data without validating the attributes.
+    ///
+    /// If the attributes provide data for different numbers of vertices, the smallest number is
+    /// used. If the data of an attribute is empty, the resulting data is empty as well.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if an attribute with an unsupported format was added.
+    /// - Panics if no attributes were added.
+    /// - Panics if attributes with different input rates were added.
+    /// - Panics if the stride requested with [`with_stride`](Self::with_stride) is smaller than
+    ///   the attributes of a vertex.
+    pub fn build_unchecked(self) -> (RuntimeVertexIter<'d>, VertexBufferInfo) {
+        if let Some(error) = &self.add_error {
+            panic!("{}", error);
+        }
+
+        let num_vertices = self.element_count().unwrap();
+
+        self.build_with(num_vertices, PadMode::Zero)
+    }
+
+    /// Builds the interleaved vertex data, padding attributes that provide data for fewer
+    /// vertices than the others.
+    ///
+    /// The number of vertices is the largest number that any attribute provides data for, and
+    /// `pad` determines the data of the missing trailing elements of the other attributes.
+    ///
+    /// Every attribute must have the same input rate.
+    pub fn build_padded(
+        self,
+        pad: PadMode,
+    ) -> Result<(RuntimeVertexIter<'d>, VertexBufferInfo), RuntimeVertexBuildError> {
+        self.validate_added()?;
+        self.validate_input_rate()?;
+        self.validate_unique_names()?;
+        self.validate_layout()?;
+        self.validate_not_empty()?;
+
+        let num_vertices = self
+            .members
+            .iter()
+            .map(RuntimeVertexMember::count)
+            .max()
+            .unwrap();
+
+        Ok(self.build_with(num_vertices, pad))
+    }
+
+    fn build_with(
+        mut self,
+        num_vertices: usize,
+        pad: PadMode,
+    ) -> (RuntimeVertexIter<'d>, VertexBufferInfo) {
+        let input_rate = self.members[0].input_rate;
+        assert!(
+            self.members
+                .iter()
+                .all(|member| member.input_rate == input_rate),
+            "attributes with different input rates were added",
+        );
+
+        assert!(
+            self.members.iter().all(|member| member.binding == 0),
+            "attributes were added to bindings other than 0",
+        );
+
+        let stride = self.stride_of(input_rate);
+        assert!(
+            self.end_of(0, input_rate) <= stride,
+            "the requested stride is smaller than the attributes of a vertex",
+        );
+
+        let endianness = self.endianness;
+        let mut slices = Vec::with_capacity(self.members.len());
+        let mut member_ranges = Vec::with_capacity(self.members.len());
+        let mut member_names = Vec::with_capacity(self.members.len());
+        let mut repeating_members = Vec::with_capacity(self.members.len());
+
+        // The iterator visits the members in the order of their offsets.
+        let mut sorted_members: Vec<_> = self.members.iter_mut().collect();
+        sorted_members.sort_by_key(|member| member.info.offset);
+
+        for member in sorted_members {
+            slices.push((member.take_bytes(endianness), member.source_stride));
+            member_ranges.push(member.byte_range());
+            member_names.push(member.name.clone());
+            repeating_members.push(member.repeat_count.is_some());
+        }
+
+        let info = VertexBufferInfo {
+            members: self
+                .members
+                .into_iter()
+                .map(|member| (member.name, member.info))
+                .collect(),
+            stride: stride as u32,
+            input_rate,
+        };
+        let iter = RuntimeVertexIter {
+            slices,
+            member_ranges,
+            member_names,
+            repeating_members,
+            stride,
+            data_index: 0,
+            data_length: num_vertices * stride,
+            pad,
+            info: info.clone(),
+        };
+
+        (iter, info)
+    }
+}
+
+// Creates the state for reading the members of `info` from `binding`. Members whose location is
+// `None` are assigned to the location after the previous member, starting at `first_location`.
+pub(super) fn vertex_input_state(
+    info: &VertexBufferInfo,
+    binding: u32,
+    locations: impl IntoIterator<Item = Option<u32>>,
+    first_location: u32,
+) -> Result<VertexInputState, RuntimeVertexBuildError> {
+    let mut attributes = Vec::new();
+    // Every location that is taken up, including the second location of 64-bit formats.
+    let mut occupied_locations = Vec::new();
+    let mut next_location = first_location;
+
+    for ((_, member), assigned_location) in info.members.iter().zip(locations) {
+        let block_size = member.format.block_size().unwrap();
+        let mut location = assigned_location.unwrap_or(next_location);
+
+        for element in 0..member.num_elements as DeviceSize {
+            for occupied in location..location + format_locations(member.format) {
+                if occupied_locations.contains(&occupied) {
+                    return Err(RuntimeVertexBuildError::LocationCollision { location: occupied });
+                }
+
+                occupied_locations.push(occupied);
+            }
+
+            attributes.push((
+                location,
+                VertexInputAttributeDescription {
+                    binding,
+                    format: member.format,
+                    offset: (member.offset as DeviceSize + element * block_size) as u32,
+                },
+            ));
+            location += format_locations(member.format);
+        }
+
+        next_location = location;
+    }
+
+    Ok(VertexInputState::new()
+        .binding(
+            binding,
+            VertexInputBindingDescription {
+                stride: info.stride,
+                input_rate: info.input_rate,
+            },
+        )
+        .attributes(attributes))
+}
+
+// The number of locations that a single element of a format takes up. 64-bit formats with more
+// than two components take up two locations.
+pub(super) fn format_locations(format: Format) -> u32 {
+    if format.block_size().unwrap_or(0) > 16 {
+        2
+    } else {
+        1
+    }
+}
+
+// The bytes of a component of `size` bytes with the value one, in native byte order.
+pub(super) fn component_one(numeric_type: NumericType, size: usize) -> Vec<u8> {
+    let bits: u64 = match numeric_type {
+        NumericType::SFLOAT => match size {
+            2 => 0x3c00,
+            4 => 0x3f80_0000,
+            _ => 0x3ff0_0000_0000_0000,
+        },
+        NumericType::UNORM | NumericType::SRGB => u64::MAX,
+        NumericType::SNORM => u64::MAX >> (65 - size * 8),
+        _ => 1,
+    };
+
+    let mut bytes = bits.to_le_bytes()[..size].to_vec();
+
+    if cfg!(target_endian = "big") {
+        bytes.reverse();
+    }
+
+    bytes
+}
+
+// Whether the components of a format are read as integers by the shader.
+#[inline]
+pub(super) fn is_integer_format(format: Format) -> bool {
+    matches!(
+        format.numeric_type(),
+        Some(NumericType::UINT | NumericType::SINT)
+    )
+}
+
+#[inline]
+pub(super) fn align_up(value: usize, alignment: usize) -> usize {
+    match value % alignment {
+        0 => value,
+        remainder => value + alignment - remainder,
+    }
+}
+
+/// The byte order of the components of data built by [`RuntimeVertexBuilder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// The byte order of the host, which the data is already in.
+    Native,
+
+    /// Little-endian byte order.
+    Little,
+
+    /// Big-endian byte order.
+    Big,
+}
+
+impl Endianness {
+    // Whether the bytes of the components must be reversed to get this byte order.
+    #[inline]
+    fn is_swapped(self) -> bool {
+        match self {
+            Endianness::Native => false,
+            Endianness::Little => cfg!(target_endian = "big"),
+            Endianness::Big => cfg!(target_endian = "little"),
+        }
+    }
+}
+
+impl Default for Endianness {
+    /// Returns [`Endianness::Native`].
+    #[inline]
+    fn default() -> Self {
+        Endianness::Native
+    }
+}
+
+/// How [`RuntimeVertexBuilder`] aligns the offsets of attributes with `UINT` and `SINT` formats
+/// to the size of their components.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntegerAlignment {
+    /// The offsets are not checked.
+    Ignore,
+
+    /// Building returns [`RuntimeVertexBuildError::MisalignedAttribute`] if an offset is not a
+    /// multiple of the component size.
+    Check,
+
+    /// Padding bytes are inserted before the attributes, like
+    /// [`add_aligned`](RuntimeVertexBuilder::add_aligned) does. Offsets that were given
+    /// explicitly are still checked like [`Check`](Self::Check) does.
+    Pad,
+}
+
+impl Default for IntegerAlignment {
+    /// Returns [`IntegerAlignment::Ignore`].
+    #[inline]
+    fn default() -> Self {
+        IntegerAlignment::Ignore
+    }
+}
+
+/// How [`RuntimeVertexBuilder::build_padded`] fills in the data of attributes that provide data
+/// for fewer vertices than the others.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PadMode {
+    /// The missing elements are filled with zero bytes.
+    Zero,
+
+    /// The last element of the attribute is repeated.
+    Repeat,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Endianness, IntegerAlignment, OwnedRuntimeVertexBuilder, PadMode, RuntimeVertexBuilder,
+    };
+    use crate::{
+        format::Format,
+        pipeline::graphics::vertex_input::{
+            definition::tests::{float_type, shader_interface},
+            runtime::tests::{assert_interleaved_eq, ATTRIBUTE_POSITION, ATTRIBUTE_UV},
+            DeinterleaveError, IncompatibleVertexDefinitionError, OwnedRuntimeVertexIter,
+            RuntimeVertexBuildError, VertexAttribute, VertexBufferInfo, VertexDefinition,
+            VertexInputRate,
+        },
+        sampler::{ComponentMapping, ComponentSwizzle},
+        shader::ShaderInterfaceEntryType,
+    };
+    use std::io::Read;
+
+    #[test]
+    fn interleave() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+
+        assert_eq!(info.stride, 20);
+        assert_eq!(info.members[0].0, "position");
+        assert_eq!(info.members[0].1.offset, 0);
+        assert_eq!(info.members[1].0, "uv");
+        assert_eq!(info.members[1].1.offset, 12);
+        assert_eq!(iter.len(), 40);
+
+        let mut expected = Vec::new();
+        for (position, uv) in positions.iter().zip(&uvs) {
+            expected.extend_from_slice(bytemuck::bytes_of(position));
+            expected.extend_from_slice(bytemuck::bytes_of(uv));
+        }
+        assert_interleaved_eq(iter, &expected);
+    }
+
+    #[test]
+    fn build_errors() {
+        assert_eq!(
+            RuntimeVertexBuilder::new().build().unwrap_err(),
+            RuntimeVertexBuildError::NoAttributes,
+        );
+
+        let positions = [[0.0f32; 3]; 3];
+        let uvs = [[0.0f32; 2]; 2];
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add::<[f32; 2]>(ATTRIBUTE_UV, &[])
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::EmptySlice {
+                attribute: "uv".into(),
+            },
+        );
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add(ATTRIBUTE_UV, &uvs)
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::MismatchedVertexCounts {
+                counts: vec![("position".into(), 3), ("uv".into(), 2)],
+            },
+        );
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add(ATTRIBUTE_POSITION, &positions)
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::DuplicateAttribute {
+                attribute: "position".into(),
+            },
+        );
+        assert!(RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(
+                VertexAttribute::new("Position", Format::R32G32B32_SFLOAT),
+                &positions,
+            )
+            .build()
+            .is_ok());
+
+        let (iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build_unchecked();
+        assert_eq!(iter.len(), 2 * 20);
+    }
+
+    #[test]
+    fn empty_attribute() {
+        let positions = [[0.0f32; 3]; 3];
+
+        for result in [
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add::<[f32; 2]>(ATTRIBUTE_UV, &[])
+                .build()
+                .map(|_| ()),
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add::<[f32; 2]>(ATTRIBUTE_UV, &[])
+                .build_padded(PadMode::Zero)
+                .map(|_| ()),
+            RuntimeVertexBuilder::new()
+                .add::<[f32; 2]>(ATTRIBUTE_UV, &[])
+                .into_vertex_input_state()
+                .map(|_| ()),
+        ] {
+            assert_eq!(
+                result.unwrap_err(),
+                RuntimeVertexBuildError::EmptySlice {
+                    attribute: "uv".into(),
+                },
+            );
+        }
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add::<[f32; 3]>(ATTRIBUTE_POSITION, &[])
+            .build_unchecked();
+        assert_eq!(iter.len(), 0);
+        assert_eq!(info.stride, 12);
+    }
+
+    #[test]
+    fn per_input_rate() {
+        const ATTRIBUTE_OFFSET: VertexAttribute =
+            VertexAttribute::new("offset", Format::R32G32_SFLOAT);
+
+        let positions = [[0.0f32; 3]; 3];
+        let uvs = [[1.0f32; 2]; 3];
+        let offsets = [[2.0f32; 2]; 2];
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add_instanced(ATTRIBUTE_OFFSET, &offsets, 1)
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::MixedInputRates,
+        );
+
+        let buffers = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add_instanced(ATTRIBUTE_OFFSET, &offsets, 4)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build_per_input_rate()
+            .unwrap();
+        assert_eq!(buffers.len(), 2);
+
+        let (vertex_iter, vertex_info) = &buffers[0];
+        assert_eq!(vertex_info.input_rate, VertexInputRate::Vertex);
+        assert_eq!(vertex_info.stride, 20);
+        assert_eq!(vertex_info.members[1].0, "uv");
+        assert_eq!(vertex_info.members[1].1.offset, 12);
+        assert_eq!(vertex_iter.len(), 3 * 20);
+
+        let (instance_iter, instance_info) = &buffers[1];
+        assert_eq!(
+            instance_info.input_rate,
+            VertexInputRate::Instance { divisor: 4 },
+        );
+        assert_eq!(instance_info.stride, 8);
+        assert_eq!(instance_info.members[0].1.offset, 0);
+        assert_eq!(instance_iter.len(), 2 * 8);
+    }
+
+    #[test]
+    fn instance_divisor() {
+        const ATTRIBUTE_OFFSET: VertexAttribute =
+            VertexAttribute::new("offset", Format::R32G32_SFLOAT);
+
+        let offsets = [[0.0f32; 2]; 2];
+
+        let (_, state) = RuntimeVertexBuilder::new()
+            .add_instanced(ATTRIBUTE_OFFSET, &offsets, 3)
+            .into_vertex_input_state()
+            .unwrap();
+        assert_eq!(
+            state.bindings[&0].input_rate,
+            VertexInputRate::Instance { divisor: 3 },
+        );
+
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add_instanced(ATTRIBUTE_OFFSET, &offsets, 3)
+            .build()
+            .unwrap();
+        let interface = shader_interface(&[("offset", 0, float_type(2, 1))]);
+        let state = info.definition(&interface).unwrap();
+        assert_eq!(
+            state.bindings[&0].input_rate,
+            VertexInputRate::Instance { divisor: 3 },
+        );
+    }
+
+    #[test]
+    fn padded() {
+        const ATTRIBUTE_INDEX: VertexAttribute = VertexAttribute::new("index", Format::R8_UINT);
+        const ATTRIBUTE_WEIGHT: VertexAttribute = VertexAttribute::new("weight", Format::R8_UNORM);
+
+        let indices = [1u8, 2, 3];
+        let weights = [4u8];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_INDEX, &indices)
+            .add(ATTRIBUTE_WEIGHT, &weights)
+            .build_padded(PadMode::Zero)
+            .unwrap();
+        assert_eq!(info.stride, 2);
+        assert_eq!(iter.collect::<Vec<u8>>(), [1, 4, 2, 0, 3, 0]);
+
+        let (iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_INDEX, &indices)
+            .add(ATTRIBUTE_WEIGHT, &weights)
+            .build_padded(PadMode::Repeat)
+            .unwrap();
+        assert_eq!(iter.collect::<Vec<u8>>(), [1, 4, 2, 4, 3, 4]);
+    }
+
+    #[test]
+    fn with_stride() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .with_stride(16)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 16);
+
+        let mut expected = Vec::new();
+        for position in &positions {
+            expected.extend_from_slice(bytemuck::bytes_of(position));
+            expected.extend_from_slice(&[0; 4]);
+        }
+        assert_interleaved_eq(iter, &expected);
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .with_stride(8)
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::StrideTooSmall {
+                attribute: "position".to_owned(),
+                stride: 8,
+                required: 12,
+            },
+        );
+    }
+
+    #[test]
+    fn endianness() {
+        let positions = [[1.0f32, 2.0, 3.0]];
+        let indices = [0x0102u16];
+        let flags = [0xabu8];
+
+        let build = |endianness| {
+            let (iter, _) = RuntimeVertexBuilder::new()
+                .with_endianness(endianness)
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add(VertexAttribute::new("index", Format::R16_UINT), &indices)
+                .add(VertexAttribute::new("flags", Format::R8_UINT), &flags)
+                .build()
+                .unwrap();
+            iter.collect_vec()
+        };
+        let expected = |to_bytes: fn(f32) -> [u8; 4], index: [u8; 2]| {
+            let mut data: Vec<u8> = [1.0, 2.0, 3.0].into_iter().flat_map(to_bytes).collect();
+            data.extend_from_slice(&index);
+            data.push(0xab);
+            data
+        };
+
+        assert_eq!(
+            build(Endianness::Big),
+            expected(f32::to_be_bytes, [0x01, 0x02]),
+        );
+        assert_eq!(
+            build(Endianness::Little),
+            expected(f32::to_le_bytes, [0x02, 0x01]),
+        );
+        assert_eq!(
+            build(Endianness::Native),
+            expected(f32::to_ne_bytes, 0x0102u16.to_ne_bytes()),
+        );
+
+        let buffers = RuntimeVertexBuilder::new()
+            .with_endianness(Endianness::Big)
+            .add(VertexAttribute::new("index", Format::R16_UINT), &indices)
+            .build_separate()
+            .unwrap();
+        assert_eq!(buffers[0].0, [0x01, 0x02]);
+    }
+
+    #[test]
+    fn extend() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let more_positions = [[7.0f32, 8.0, 9.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0], [11.0, 12.0]];
+
+        let mut builder = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs);
+        builder.extend("position", &more_positions).unwrap();
+        assert_eq!(builder.vertex_count(), Some(3));
+
+        assert_eq!(
+            builder.extend("normal", &more_positions).unwrap_err(),
+            RuntimeVertexBuildError::UnknownAttribute {
+                attribute: "normal".into(),
+            },
+        );
+        assert_eq!(
+            builder.extend("position", &uvs).unwrap_err(),
+            RuntimeVertexBuildError::ElementSizeMismatch {
+                attribute: "position".into(),
+                expected: 12,
+                provided: 8,
+            },
+        );
+
+        let (iter, _) = builder.build().unwrap();
+        let mut expected = Vec::new();
+        for (position, uv) in positions.iter().chain(&more_positions).zip(&uvs) {
+            expected.extend_from_slice(bytemuck::bytes_of(position));
+            expected.extend_from_slice(bytemuck::bytes_of(uv));
+        }
+        assert_interleaved_eq(iter, &expected);
+    }
+
+    #[test]
+    fn append_interleaved() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+        let builder = || {
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add(ATTRIBUTE_UV, &uvs)
+        };
+
+        let (iter, info) = builder().build().unwrap();
+        let chunk = iter.collect_vec();
+
+        let mut merged = builder();
+        merged.append_interleaved(&chunk, &info).unwrap();
+        assert_eq!(merged.vertex_count(), Some(4));
+        assert_eq!(merged.build().unwrap().0.collect_vec(), chunk.repeat(2));
+
+        let mut builder = builder();
+        assert_eq!(
+            builder.append_interleaved(&chunk[..30], &info).unwrap_err(),
+            RuntimeVertexBuildError::InvalidInterleavedData(DeinterleaveError::InvalidLength {
+                length: 30,
+                stride: 20,
+            }),
+        );
+
+        let mut wider = info.clone();
+        wider.stride = 24;
+        assert_eq!(
+            builder.append_interleaved(&chunk, &wider).unwrap_err(),
+            RuntimeVertexBuildError::LayoutMismatch { attribute: None },
+        );
+
+        let mut renamed = info.clone();
+        renamed.members[1].0 = "texcoord".into();
+        assert_eq!(
+            builder.append_interleaved(&chunk, &renamed).unwrap_err(),
+            RuntimeVertexBuildError::LayoutMismatch {
+                attribute: Some("uv".into()),
+            },
+        );
+        assert_eq!(builder.vertex_count(), Some(2));
+    }
+
+    #[test]
+    fn vertex_count() {
+        let positions = [[0.0f32; 3]; 3];
+        let uvs = [[0.0f32; 2]; 2];
+
+        let builder = RuntimeVertexBuilder::with_capacity(2);
+        assert_eq!(builder.vertex_count(), None);
+        assert_eq!(builder.byte_len(), 0);
+
+        let builder = builder
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs);
+        assert_eq!(builder.vertex_count(), Some(2));
+        assert_eq!(builder.byte_len(), 2 * 20);
+        assert_eq!(builder.build_unchecked().0.len(), 2 * 20);
+    }
+
+    #[test]
+    fn byte_len_mixed_input_rates() {
+        let positions = [[0.0f32; 3]; 3];
+        let offsets = [[0.0f32; 2]; 2];
+
+        let builder = RuntimeVertexBuilder::new()
+            .add_instanced(ATTRIBUTE_UV, &offsets, 1)
+            .add(ATTRIBUTE_POSITION, &positions);
+        assert_eq!(builder.vertex_count(), Some(3));
+        assert_eq!(builder.byte_len(), 3 * 12);
+
+        let buffers = builder.build_per_input_rate().unwrap();
+        let (iter, info) = &buffers[1];
+        assert_eq!(info.input_rate, VertexInputRate::Vertex);
+        assert_eq!(iter.len(), 3 * 12);
+
+        let instanced_only = RuntimeVertexBuilder::new().add_instanced(ATTRIBUTE_UV, &offsets, 1);
+        assert_eq!(instanced_only.vertex_count(), None);
+        assert_eq!(instanced_only.byte_len(), 0);
+        assert_eq!(instanced_only.build_unchecked().0.len(), 2 * 8);
+    }
+
+    #[test]
+    fn aligned() {
+        const ATTRIBUTE_FLAG: VertexAttribute = VertexAttribute::new("flag", Format::R8_UINT);
+        const ATTRIBUTE_WEIGHT: VertexAttribute =
+            VertexAttribute::new("weight", Format::R32_SFLOAT);
+
+        let flags = [1u8, 2];
+        let weights = [3.0f32, 4.0];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_FLAG, &flags)
+            .add_aligned(ATTRIBUTE_WEIGHT, &weights)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 8);
+        assert_eq!(info.members[1].1.offset, 4);
+
+        let mut expected = Vec::new();
+        for (&flag, weight) in flags.iter().zip(&weights) {
+            expected.extend_from_slice(&[flag, 0, 0, 0]);
+            expected.extend_from_slice(bytemuck::bytes_of(weight));
+        }
+        assert_interleaved_eq(iter, &expected);
+
+        // The stride is rounded up so that the weight stays aligned in every vertex.
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add_aligned(ATTRIBUTE_WEIGHT, &weights)
+            .add(ATTRIBUTE_FLAG, &flags)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 8);
+        assert_eq!(info.members[1].1.offset, 4);
+
+        let mut expected = Vec::new();
+        for (&flag, weight) in flags.iter().zip(&weights) {
+            expected.extend_from_slice(bytemuck::bytes_of(weight));
+            expected.extend_from_slice(&[flag, 0, 0, 0]);
+        }
+        assert_interleaved_eq(iter, &expected);
+    }
+
+    #[test]
+    fn integer_alignment() {
+        const ATTRIBUTE_INDEX: VertexAttribute = VertexAttribute::new("index", Format::R8_UINT);
+        const ATTRIBUTE_COLOR: VertexAttribute = VertexAttribute::new("color", Format::R32_UINT);
+
+        let indices = [1u8, 2];
+        let colors = [3u32, 4];
+        let builder = |integer_alignment| {
+            RuntimeVertexBuilder::new()
+                .with_integer_alignment(integer_alignment)
+                .add(ATTRIBUTE_INDEX, &indices)
+                .add(ATTRIBUTE_COLOR, &colors)
+        };
+
+        let (_, info) = builder(IntegerAlignment::Ignore).build().unwrap();
+        assert_eq!(info.member("color").unwrap().offset, 1);
+        assert_eq!(info.stride, 5);
+
+        assert_eq!(
+            builder(IntegerAlignment::Check).build().unwrap_err(),
+            RuntimeVertexBuildError::MisalignedAttribute {
+                attribute: "color".to_owned(),
+                offset: 1,
+                required: 4,
+            },
+        );
+
+        let (_, info) = builder(IntegerAlignment::Pad).build().unwrap();
+        assert_eq!(info.member("color").unwrap().offset, 4);
+        assert_eq!(info.stride, 8);
+
+        // Explicit offsets are not moved.
+        let result = RuntimeVertexBuilder::new()
+            .with_integer_alignment(IntegerAlignment::Pad)
+            .add(ATTRIBUTE_INDEX, &indices)
+            .add_at(ATTRIBUTE_COLOR, &colors, 2)
+            .build();
+        assert!(matches!(
+            result,
+            Err(RuntimeVertexBuildError::MisalignedAttribute { offset: 2, .. }),
+        ));
+    }
+
+    #[test]
+    fn add_bytes() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let expected: Vec<u8> = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap()
+            .0
+            .collect();
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add_bytes(ATTRIBUTE_POSITION, bytemuck::cast_slice(&positions), 12)
+            .add_bytes(ATTRIBUTE_UV, bytemuck::cast_slice(&uvs), 8)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 20);
+        assert_interleaved_eq(iter, &expected);
+    }
+
+    #[test]
+    fn add_strided() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+        let uvs = [[10.0f32, 11.0], [12.0, 13.0], [14.0, 15.0]];
+
+        // Positions followed by unrelated data, without any data after the last position.
+        let mut source: Vec<f32> = positions
+            .iter()
+            .flat_map(|p| [p[0], p[1], p[2], -1.0])
+            .collect();
+        source.pop();
+
+        let expected: Vec<u8> = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap()
+            .0
+            .collect();
+        let builder = || {
+            RuntimeVertexBuilder::new()
+                .add_strided(ATTRIBUTE_POSITION, bytemuck::cast_slice(&source), 16)
+                .add(ATTRIBUTE_UV, &uvs)
+        };
+        let (iter, info) = builder().build().unwrap();
+        assert_eq!(info.stride, 20);
+        assert_eq!(info.members[0].1.num_elements, 1);
+        assert_interleaved_eq(iter, &expected);
+
+        // Only the components are swapped, not the bytes between the elements.
+        let swapped: Vec<u8> = builder()
+            .with_endianness(Endianness::Big)
+            .build()
+            .unwrap()
+            .0
+            .collect();
+        let expected_swapped: Vec<u8> = RuntimeVertexBuilder::new()
+            .with_endianness(Endianness::Big)
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap()
+            .0
+            .collect();
+        assert_eq!(swapped, expected_swapped);
+
+        // Appended data is spread out to the stride of the existing data.
+        let mut appended = builder();
+        appended.append_interleaved(&expected, &info).unwrap();
+        let (iter, _) = appended.build().unwrap();
+        assert_eq!(iter.collect_vec(), [&expected[..], &expected[..]].concat());
+
+        // Only the elements themselves are part of the separate data.
+        let (data, info) = builder().build_separate().unwrap().remove(0);
+        assert_eq!(info.stride, 12);
+        assert_eq!(data, bytemuck::cast_slice::<_, u8>(&positions));
+
+        let (iter, _) = builder().build().unwrap();
+        assert!(iter
+            .attribute_bytes("position")
+            .unwrap()
+            .eq(bytemuck::cast_slice::<_, u8>(&positions).iter().copied()));
+
+        // The parts keep the stride of the source data.
+        let (parts, _) = builder().build().unwrap().0.into_parts();
+        assert_eq!(parts[0].1, 16);
+        assert_eq!(parts[0].0[..44], *bytemuck::cast_slice::<_, u8>(&source));
+    }
+
+    #[test]
+    fn add_strided_mismatch() {
+        let source = [0u8; 40];
+
+        for (stride, len) in [(8, 40), (16, 40)] {
+            let result = RuntimeVertexBuilder::new()
+                .add_strided(ATTRIBUTE_POSITION, &source[..len], stride)
+                .build();
+            assert_eq!(
+                result.unwrap_err(),
+                RuntimeVertexBuildError::SourceStrideMismatch {
+                    attribute: "position".to_owned(),
+                    stride,
+                    element_size: 12,
+                    data_len: len,
+                },
+            );
+        }
+
+        assert!(RuntimeVertexBuilder::new()
+            .add_strided(ATTRIBUTE_POSITION, &source[..28], 16)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn array_attribute() {
+        const ATTRIBUTE_WEIGHTS: VertexAttribute =
+            VertexAttribute::new_array("weights", Format::R32G32B32A32_SFLOAT, 2);
+
+        let weights = [[0.0f32; 8]; 2];
+
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_WEIGHTS, &weights)
+            .build()
+            .unwrap();
+        assert_eq!(info.members[0].1.num_elements, 2);
+    }
+
+    #[test]
+    fn array_attribute_size_mismatch() {
+        const ATTRIBUTE_WEIGHTS: VertexAttribute =
+            VertexAttribute::new_array("weights", Format::R32G32B32A32_SFLOAT, 3);
+
+        let weights = [[0.0f32; 8]; 2];
+        let result = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_WEIGHTS, &weights)
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            RuntimeVertexBuildError::FormatSizeMismatch {
+                attribute: "weights".to_owned(),
+                expected: 48,
+                provided: 32,
+            },
+        );
+    }
+
+    #[test]
+    fn data_size_mismatch() {
+        let data = [0u8; 20];
+
+        let result = RuntimeVertexBuilder::new()
+            .add_bytes(ATTRIBUTE_POSITION, &data, 12)
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            RuntimeVertexBuildError::DataSizeMismatch {
+                attribute: "position".to_owned(),
+                data_len: 20,
+                element_size: 12,
+            },
+        );
+    }
+
+    #[test]
+    fn add_unchecked() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let expected = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+        let unchecked = RuntimeVertexBuilder::new()
+            .add_unchecked(ATTRIBUTE_POSITION, &positions)
+            .add_unchecked(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+        assert_eq!(unchecked.1, expected.1);
+        assert_eq!(unchecked.0.collect_vec(), expected.0.collect_vec());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "size of attribute `weights` does not match its format")]
+    fn add_unchecked_size_mismatch() {
+        const ATTRIBUTE_WEIGHTS: VertexAttribute =
+            VertexAttribute::new_array("weights", Format::R32G32B32A32_SFLOAT, 3);
+
+        let weights = [[0.0f32; 8]; 2];
+        RuntimeVertexBuilder::new().add_unchecked(ATTRIBUTE_WEIGHTS, &weights);
+    }
+
+    #[test]
+    fn stride_format_mismatch() {
+        let normals = [[0.0f32; 3]; 2];
+
+        let result = RuntimeVertexBuilder::new()
+            .add(
+                VertexAttribute::new("normal", Format::R16G16B16A16_SFLOAT),
+                &normals,
+            )
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            RuntimeVertexBuildError::StrideFormatMismatch {
+                attribute: "normal".to_owned(),
+                expected: 8,
+                provided: 12,
+            },
+        );
+    }
+
+    #[test]
+    fn matrix_attribute() {
+        let models = [[[0.0f32; 4]; 4]; 2];
+        let uvs = [[0.0f32; 2]; 2];
+
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add_matrix("model", Format::R32G32B32A32_SFLOAT, 4, &models)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 72);
+
+        for column in 0..4 {
+            let (name, member) = &info.members[column];
+            assert_eq!(*name, format!("model[{}]", column));
+            assert_eq!(member.offset, column * 16);
+            assert_eq!(member.num_elements, 1);
+        }
+
+        let interface =
+            shader_interface(&[("model", 0, float_type(4, 4)), ("uv", 4, float_type(2, 1))]);
+        let state = info.definition(&interface).unwrap();
+        assert_eq!(state.attributes.len(), 5);
+
+        for column in 0..4 {
+            assert_eq!(state.attributes[&column].offset, column * 16);
+        }
+
+        assert_eq!(state.attributes[&4].offset, 64);
+    }
+
+    #[test]
+    fn matrix_attribute_size_mismatch() {
+        let models = [[[0.0f32; 4]; 3]; 2];
+        let result = RuntimeVertexBuilder::new()
+            .add_matrix("model", Format::R32G32B32A32_SFLOAT, 4, &models)
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            RuntimeVertexBuildError::MatrixSizeMismatch {
+                attribute: "model".to_owned(),
+                matrix_size: 64,
+                element_size: 48,
+            },
+        );
+    }
+
+    #[test]
+    fn normalized_attribute() {
+        let colors = [[255u8, 0, 0, 255], [0, 255, 0, 255]];
+
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add(
+                VertexAttribute::new("color", Format::R8G8B8A8_UNORM),
+                &colors,
+            )
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 4);
+        assert_eq!(info.members[0].1.num_elements, 1);
+
+        let interface = shader_interface(&[("color", 0, float_type(4, 1))]);
+        let state = info.definition(&interface).unwrap();
+        assert_eq!(state.attributes.len(), 1);
+        assert_eq!(state.attributes[&0].format, Format::R8G8B8A8_UNORM);
+    }
+
+    #[test]
+    fn packed_attribute() {
+        const ATTRIBUTE_NORMAL: VertexAttribute =
+            VertexAttribute::new("normal", Format::A2B10G10R10_UNORM_PACK32);
+
+        let flags = [1u8, 2];
+        let normals = [0x3ff0_0000u32, 0x000f_fc00];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add(VertexAttribute::new("flags", Format::R8_UINT), &flags)
+            .add_aligned(ATTRIBUTE_NORMAL, &normals)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 8);
+        assert_eq!(info.members[1].1.offset, 4);
+        assert_eq!(info.members[1].1.num_elements, 1);
+
+        let data = iter.collect_vec();
+        assert_eq!(&data[4..8], &0x3ff0_0000u32.to_ne_bytes());
+        assert_eq!(&data[12..16], &0x000f_fc00u32.to_ne_bytes());
+    }
+
+    #[test]
+    fn packed_attribute_size_mismatch() {
+        let normals = [[0u32; 2]; 2];
+
+        let result = RuntimeVertexBuilder::new()
+            .add(
+                VertexAttribute::new("normal", Format::A2B10G10R10_UNORM_PACK32),
+                &normals,
+            )
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            RuntimeVertexBuildError::FormatSizeMismatch {
+                attribute: "normal".to_owned(),
+                expected: 4,
+                provided: 8,
+            },
+        );
+    }
+
+    #[test]
+    fn double_attribute() {
+        let positions = [[1.0f64, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add(
+                VertexAttribute::new("position", Format::R64G64B64_SFLOAT),
+                &positions,
+            )
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 32);
+        assert_eq!(info.members[0].1.num_elements, 1);
+
+        let dvec3 = ShaderInterfaceEntryType {
+            is_64bit: true,
+            ..float_type(3, 1)
+        };
+        let interface = shader_interface(&[("position", 0, dvec3), ("uv", 2, float_type(2, 1))]);
+        let state = info.definition(&interface).unwrap();
+        assert_eq!(state.attributes.len(), 2);
+        assert_eq!(state.attributes[&0].format, Format::R64G64B64_SFLOAT);
+        assert_eq!(state.attributes[&2].offset, 24);
+
+        let dvec3_array = ShaderInterfaceEntryType {
+            num_elements: 2,
+            ..dvec3
+        };
+        let interface = shader_interface(&[("position", 0, dvec3_array)]);
+        assert_eq!(
+            info.definition(&interface).unwrap_err(),
+            IncompatibleVertexDefinitionError::LocationOutOfRange {
+                attribute: "position".into(),
+                shader_locations: 4,
+                provided_locations: 2,
+            },
+        );
+    }
+
+    #[test]
+    fn into_vertex_input_state() {
+        let positions = [[1.0f64, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let matrices = [[[0.0f32; 2]; 2]; 2];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (iter, state) = RuntimeVertexBuilder::new()
+            .add(
+                VertexAttribute::new("position", Format::R64G64B64_SFLOAT),
+                &positions,
+            )
+            .add(
+                VertexAttribute::new_array("matrix", Format::R32G32_SFLOAT, 2),
+                &matrices,
+            )
+            .add(ATTRIBUTE_UV, &uvs)
+            .into_vertex_input_state_at(1)
+            .unwrap();
+        assert_eq!(iter.len(), 2 * 48);
+        assert_eq!(state.bindings[&0].stride, 48);
+        assert_eq!(state.bindings[&0].input_rate, VertexInputRate::Vertex);
+
+        let mut locations: Vec<(u32, u32)> = state
+            .attributes
+            .iter()
+            .map(|(&location, attribute)| (location, attribute.offset))
+            .collect();
+        locations.sort_unstable();
+        assert_eq!(locations, [(1, 0), (3, 24), (4, 32), (5, 40)]);
+    }
+
+    #[test]
+    fn build_multi() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+        let offsets = [[11.0f32, 12.0]];
+        let builder = || {
+            RuntimeVertexBuilder::new()
+                .add_instanced_to_binding(
+                    1,
+                    VertexAttribute::new("offset", Format::R32G32_SFLOAT).with_offset(4),
+                    &offsets,
+                    1,
+                )
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add_to_binding(0, ATTRIBUTE_UV, &uvs)
+        };
+
+        let (buffers, state) = builder().build_multi().unwrap();
+        assert_eq!(buffers.len(), 2);
+
+        let (binding, iter, info) = &buffers[0];
+        assert_eq!(*binding, 0);
+        assert_eq!(info.stride, 20);
+        assert_eq!(info.input_rate, VertexInputRate::Vertex);
+        assert_eq!(
+            bytemuck::cast_slice::<_, f32>(&iter.clone().collect_vec()),
+            &[1.0, 2.0, 3.0, 7.0, 8.0, 4.0, 5.0, 6.0, 9.0, 10.0],
+        );
+
+        let (binding, iter, info) = &buffers[1];
+        assert_eq!(*binding, 1);
+        assert_eq!(info.stride, 12);
+        assert_eq!(info.input_rate, VertexInputRate::Instance { divisor: 1 });
+        assert_eq!(
+            bytemuck::cast_slice::<_, f32>(&iter.clone().collect_vec()),
+            &[0.0, 11.0, 12.0],
+        );
+
+        assert_eq!(state.bindings.len(), 2);
+        assert_eq!(state.bindings[&0].stride, 20);
+        assert_eq!(state.bindings[&1].stride, 12);
+        assert_eq!(
+            state.bindings[&1].input_rate,
+            VertexInputRate::Instance { divisor: 1 },
+        );
+        assert_eq!(state.attributes.len(), 3);
+        assert_eq!(state.attributes[&0].binding, 0);
+        assert_eq!(state.attributes[&1].binding, 0);
+        assert_eq!(state.attributes[&1].offset, 12);
+        assert_eq!(state.attributes[&2].binding, 1);
+        assert_eq!(state.attributes[&2].offset, 4);
+
+        assert_eq!(
+            builder().build_per_input_rate().unwrap_err(),
+            RuntimeVertexBuildError::MultipleBindings { binding: 1 },
+        );
+    }
+
+    #[test]
+    fn build_multi_integer_alignment() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let flags = [1u8, 2];
+        let ids = [3u32, 4];
+        let offsets = [[5.0f32, 6.0]];
+        let layers = [7u16];
+
+        let (buffers, state) = RuntimeVertexBuilder::new()
+            .with_integer_alignment(IntegerAlignment::Pad)
+            .add_to_binding(1, VertexAttribute::new("flag", Format::R8_UINT), &flags)
+            .add_instanced_to_binding(
+                2,
+                VertexAttribute::new("offset", Format::R32G32_SFLOAT),
+                &offsets,
+                1,
+            )
+            .add_to_binding(1, VertexAttribute::new("id", Format::R32_UINT), &ids)
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add_instanced_to_binding(
+                2,
+                VertexAttribute::new("layer", Format::R16_UINT),
+                &layers,
+                1,
+            )
+            .build_multi()
+            .unwrap();
+        assert_eq!(buffers.len(), 3);
+
+        // Each binding is laid out on its own, and integer attributes are padded within it.
+        let (_, _, info) = &buffers[0];
+        assert_eq!(info.offset_of("position"), Some(0));
+        assert_eq!(info.stride, 12);
+
+        let (_, iter, info) = &buffers[1];
+        assert_eq!(info.offset_of("flag"), Some(0));
+        assert_eq!(info.offset_of("id"), Some(4));
+        assert_eq!(info.stride, 8);
+        let data = iter.clone().collect_vec();
+        assert_eq!(data.len(), 16);
+        assert_eq!(data[8], 2);
+        assert_eq!(data[12..], 4u32.to_ne_bytes());
+
+        let (_, _, info) = &buffers[2];
+        assert_eq!(info.offset_of("offset"), Some(0));
+        assert_eq!(info.offset_of("layer"), Some(8));
+        assert_eq!(info.input_rate, VertexInputRate::Instance { divisor: 1 });
+
+        assert_eq!(state.bindings[&1].stride, 8);
+        assert_eq!(state.bindings[&2].stride, 10);
+    }
+
+    #[test]
+    fn location_map() {
+        let positions = [[1.0f64, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+        let offsets = [[11.0f32, 12.0]];
+        let builder = RuntimeVertexBuilder::new()
+            .add_instanced_to_binding(
+                1,
+                VertexAttribute::new("offset", Format::R32G32_SFLOAT),
+                &offsets,
+                1,
+            )
+            .add(
+                VertexAttribute::new("position", Format::R64G64B64_SFLOAT),
+                &positions,
+            )
+            .add(ATTRIBUTE_UV, &uvs);
+
+        let map = builder.location_map();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["position"], 0);
+        assert_eq!(map["uv"], 2);
+        assert_eq!(map["offset"], 3);
+
+        let (buffers, state) = builder.build_multi().unwrap();
+        for (_, _, info) in &buffers {
+            for (name, member) in &info.members {
+                assert_eq!(state.attributes[&map[name]].format, member.format);
+            }
+        }
+
+        let map = RuntimeVertexBuilder::new()
+            .add_at_location(5, ATTRIBUTE_POSITION, &[[1.0f32, 2.0, 3.0]])
+            .add_at_location(2, ATTRIBUTE_UV, &[[4.0f32, 5.0]])
+            .location_map();
+        assert_eq!(map["position"], 5);
+        assert_eq!(map["uv"], 2);
+    }
+
+    #[test]
+    fn add_at_location() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (_, state) = RuntimeVertexBuilder::new()
+            .add_at_location(3, ATTRIBUTE_POSITION, &positions)
+            .add_at_location(0, ATTRIBUTE_UV, &uvs)
+            .into_vertex_input_state()
+            .unwrap();
+        assert_eq!(state.attributes.len(), 2);
+        assert_eq!(state.attributes[&3].format, Format::R32G32B32_SFLOAT);
+        assert_eq!(state.attributes[&0].offset, 12);
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_at_location(3, ATTRIBUTE_POSITION, &positions)
+                .add(ATTRIBUTE_UV, &uvs)
+                .into_vertex_input_state()
+                .unwrap_err(),
+            RuntimeVertexBuildError::MixedLocationAssignment,
+        );
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_at_location(1, ATTRIBUTE_POSITION, &positions)
+                .add_at_location(1, ATTRIBUTE_UV, &uvs)
+                .into_vertex_input_state()
+                .unwrap_err(),
+            RuntimeVertexBuildError::LocationCollision { location: 1 },
+        );
+    }
+
+    #[test]
+    fn add_aliased() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (iter, state) = RuntimeVertexBuilder::new()
+            .add_aliased(0, &[4], ATTRIBUTE_POSITION, &positions)
+            .add_at_location(1, ATTRIBUTE_UV, &uvs)
+            .into_vertex_input_state()
+            .unwrap();
+        assert_eq!(iter.len(), 40);
+        assert_eq!(state.attributes.len(), 3);
+        assert_eq!(state.attributes[&0], state.attributes[&4]);
+        assert_eq!(state.attributes[&4].offset, 0);
+        assert_eq!(state.attributes[&1].offset, 12);
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_aliased(0, &[1], ATTRIBUTE_POSITION, &positions)
+                .add_at_location(1, ATTRIBUTE_UV, &uvs)
+                .into_vertex_input_state()
+                .unwrap_err(),
+            RuntimeVertexBuildError::LocationCollision { location: 1 },
+        );
+    }
+
+    #[test]
+    fn add_aliased_no_aliases() {
+        let positions = [[1.0f32, 2.0, 3.0]];
+
+        let (_, state) = RuntimeVertexBuilder::new()
+            .add_aliased(2, &[], ATTRIBUTE_POSITION, &positions)
+            .into_vertex_input_state()
+            .unwrap();
+        assert_eq!(state.attributes.len(), 1);
+        assert_eq!(state.attributes[&2].offset, 0);
+    }
+
+    #[test]
+    fn invalid_format() {
+        let positions = [[0.0f32; 3]; 2];
+        let data = [[0u8; 16]; 2];
+
+        let builder = RuntimeVertexBuilder::new()
+            .add(
+                VertexAttribute::new("compressed", Format::BC7_UNORM_BLOCK),
+                &data,
+            )
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(VertexAttribute::new("depth", Format::D16_UNORM), &data);
+        assert_eq!(
+            builder.clone().build().unwrap_err(),
+            RuntimeVertexBuildError::UnsupportedFormat {
+                attribute: "compressed".into(),
+                format: Format::BC7_UNORM_BLOCK,
+            },
+        );
+        assert!(builder.clone().build_separate().is_err());
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add(VertexAttribute::new("depth", Format::D16_UNORM), &data)
+                .build_padded(PadMode::Zero)
+                .unwrap_err(),
+            RuntimeVertexBuildError::FormatHasNoBlockSize {
+                attribute: "depth".into(),
+                format: Format::D16_UNORM,
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be used for vertex attributes")]
+    fn invalid_format_unchecked() {
+        let data = [[0u8; 16]; 2];
+
+        RuntimeVertexBuilder::new()
+            .add(
+                VertexAttribute::new("compressed", Format::BC7_UNORM_BLOCK),
+                &data,
+            )
+            .build_unchecked();
+    }
+
+    #[test]
+    fn add_at() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add_at(ATTRIBUTE_POSITION, &positions, 12)
+            .add_at(ATTRIBUTE_UV, &uvs, 0)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 24);
+        assert_eq!(info.members[0].1.offset, 12);
+        assert_eq!(info.members[1].1.offset, 0);
+
+        let mut expected = Vec::new();
+        for (position, uv) in positions.iter().zip(&uvs) {
+            expected.extend_from_slice(bytemuck::bytes_of(uv));
+            expected.extend_from_slice(&[0; 4]);
+            expected.extend_from_slice(bytemuck::bytes_of(position));
+        }
+        assert_interleaved_eq(iter, &expected);
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_at(ATTRIBUTE_POSITION, &positions, 0)
+                .add_at(ATTRIBUTE_UV, &uvs, 8)
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::OverlappingAttributes {
+                first: "position".into(),
+                second: "uv".into(),
+            },
+        );
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_at(ATTRIBUTE_UV, &uvs, 0)
+                .add_at(ATTRIBUTE_POSITION, &positions, 12)
+                .with_stride(16)
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::StrideTooSmall {
+                attribute: "position".into(),
+                stride: 16,
+                required: 24,
+            },
+        );
+    }
+
+    #[test]
+    fn add_optional() {
+        let positions = [[1.0f32, 2.0, 3.0]];
+        let normals = [[0.0f32, 0.0, 1.0]];
+        let uvs = [[4.0f32, 5.0]];
+
+        let build = |with_normals: bool| {
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add_optional(
+                    VertexAttribute::new("normal", Format::R32G32B32_SFLOAT),
+                    with_normals.then_some(&normals[..]),
+                )
+                .add(ATTRIBUTE_UV, &uvs)
+                .build()
+                .unwrap()
+        };
+
+        let (_, info) = build(true);
+        assert_eq!(info.stride, 32);
+        assert_eq!(info.member("uv").unwrap().offset, 24);
+
+        let (iter, info) = build(false);
+        assert_eq!(info.stride, 20);
+        assert!(info.member("normal").is_none());
+        assert_eq!(info.member("uv").unwrap().offset, 12);
+        assert_eq!(iter.collect_vec().len(), 20);
+    }
+
+    #[test]
+    fn add_constant() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let builder = || {
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add_constant(ATTRIBUTE_UV, [7.0f32, 8.0], 2)
+        };
+
+        let (iter, info) = builder().build().unwrap();
+        assert_eq!(info.stride, 20);
+        assert_eq!(
+            bytemuck::cast_slice::<_, f32>(&iter.clone().collect_vec()),
+            &[1.0, 2.0, 3.0, 7.0, 8.0, 4.0, 5.0, 6.0, 7.0, 8.0],
+        );
+        assert_eq!(
+            iter.attribute_bytes("uv").unwrap().collect::<Vec<_>>(),
+            bytemuck::cast_slice::<f32, u8>(&[7.0, 8.0, 7.0, 8.0]),
+        );
+
+        let buffers = builder().build_separate().unwrap();
+        assert_eq!(
+            bytemuck::cast_slice::<_, f32>(&buffers[1].0),
+            &[7.0, 8.0, 7.0, 8.0],
+        );
+
+        // Extending the attribute keeps the constant for the vertices that it was added for.
+        let more_positions = [[9.0f32, 10.0, 11.0]];
+        let more_uvs = [[12.0f32, 13.0]];
+        let mut builder = builder();
+        builder.extend("position", &more_positions).unwrap();
+        builder.extend("uv", &more_uvs).unwrap();
+        let (iter, _) = builder.build().unwrap();
+        assert_eq!(
+            bytemuck::cast_slice::<_, f32>(&iter.collect_vec())[10..],
+            [9.0, 10.0, 11.0, 12.0, 13.0],
+        );
+
+        let result = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add_constant(ATTRIBUTE_UV, [0.0f32; 2], 3)
+            .build();
+        assert!(matches!(
+            result,
+            Err(RuntimeVertexBuildError::MismatchedVertexCounts { .. }),
+        ));
+    }
+
+    #[test]
+    fn add_repeating() {
+        let positions = [[0.0f32; 3]; 5];
+        let uvs = [[0.0f32, 0.0], [1.0, 0.0], [1.0, 1.0]];
+        let builder = || {
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add_repeating(ATTRIBUTE_UV, &uvs, 5)
+        };
+
+        let (iter, _) = builder().build().unwrap();
+        let expected: [f32; 10] = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+        assert_eq!(
+            iter.clone()
+                .vertices()
+                .flat_map(|vertex| vertex[12..].to_vec())
+                .collect::<Vec<_>>(),
+            bytemuck::cast_slice::<f32, u8>(&expected),
+        );
+        assert_eq!(
+            iter.attribute_bytes("uv").unwrap().collect::<Vec<_>>(),
+            bytemuck::cast_slice::<f32, u8>(&expected),
+        );
+
+        let buffers = builder().build_separate().unwrap();
+        assert_eq!(bytemuck::cast_slice::<_, f32>(&buffers[1].0), &expected);
+
+        let result = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add_repeating::<[f32; 2]>(ATTRIBUTE_UV, &[], 5)
+            .build();
+        assert!(matches!(
+            result,
+            Err(RuntimeVertexBuildError::EmptySlice { attribute }) if attribute == "uv",
+        ));
+    }
+
+    #[test]
+    fn reordered_members() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+        let weights = [11.0f32, 12.0];
+
+        let build = || {
+            RuntimeVertexBuilder::new()
+                .add_at(
+                    VertexAttribute::new("weight", Format::R32_SFLOAT),
+                    &weights,
+                    24,
+                )
+                .add_at(ATTRIBUTE_UV, &uvs, 0)
+                .add_at(ATTRIBUTE_POSITION, &positions, 8)
+                .build()
+                .unwrap()
+                .0
+        };
+
+        let mut expected = Vec::new();
+        for ((position, uv), weight) in positions.iter().zip(&uvs).zip(&weights) {
+            expected.extend_from_slice(bytemuck::bytes_of(uv));
+            expected.extend_from_slice(bytemuck::bytes_of(position));
+            expected.extend_from_slice(&[0; 4]);
+            expected.extend_from_slice(bytemuck::bytes_of(weight));
+        }
+        assert_eq!(build().collect::<Vec<u8>>(), expected);
+
+        // Reading in chunks that don't line up with the members must give the same result.
+        let mut iter = build();
+        let mut bytes = Vec::new();
+        let mut chunk = [0; 7];
+        loop {
+            let len = iter.read(&mut chunk).unwrap();
+            if len == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&chunk[..len]);
+        }
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn layout() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0]];
+
+        let builder = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .with_stride(24);
+
+        // The data doesn't have to be complete to get the layout.
+        let layout = builder.layout().unwrap();
+        assert_eq!(layout.stride, 24);
+        assert_eq!(layout.member("uv").unwrap().offset, 12);
+
+        let (_, info) = builder.build_padded(PadMode::Zero).unwrap();
+        assert_eq!(layout, info);
+
+        assert_eq!(
+            RuntimeVertexBuilder::new().layout().unwrap_err(),
+            RuntimeVertexBuildError::NoAttributes,
+        );
+    }
+
+    #[test]
+    fn from_vertices() {
+        use bytemuck::{Pod, Zeroable};
+
+        #[derive(Clone, Copy, Zeroable, Pod)]
+        #[repr(C)]
+        struct Vertex {
+            position: [f32; 3],
+            uv: [f32; 2],
+        }
+
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+        let (expected_iter, expected_info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+
+        let vertices = positions
+            .iter()
+            .zip(&uvs)
+            .map(|(&position, &uv)| Vertex { position, uv });
+        let (data, info) =
+            RuntimeVertexBuilder::from_vertices(&[ATTRIBUTE_POSITION, ATTRIBUTE_UV], vertices)
+                .unwrap();
+        assert_eq!(info, expected_info);
+        assert_eq!(data, expected_iter.collect_vec());
+
+        let (data, info) = RuntimeVertexBuilder::from_vertices(
+            &[ATTRIBUTE_POSITION, ATTRIBUTE_UV],
+            std::iter::empty::<Vertex>(),
+        )
+        .unwrap();
+        assert!(data.is_empty());
+        assert_eq!(info, expected_info);
+
+        // The attributes are checked whether or not there are any vertices.
+        let vertex = Vertex {
+            position: [0.0; 3],
+            uv: [0.0; 2],
+        };
+
+        for vertices in [vec![vertex, vertex], vec![]] {
+            assert_eq!(
+                RuntimeVertexBuilder::from_vertices(&[ATTRIBUTE_POSITION], vertices.clone())
+                    .unwrap_err(),
+                RuntimeVertexBuildError::StructSizeMismatch {
+                    attributes_size: 12,
+                    struct_size: 20,
+                },
+            );
+            assert_eq!(
+                RuntimeVertexBuilder::from_vertices(
+                    &[ATTRIBUTE_POSITION, ATTRIBUTE_UV.with_offset(16)],
+                    vertices,
+                )
+                .unwrap_err(),
+                RuntimeVertexBuildError::AttributeOutsideStruct {
+                    attribute: "uv".to_owned(),
+                    end: 24,
+                    struct_size: 20,
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn build_chunked() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+        let uvs = [[10.0f32, 11.0], [12.0, 13.0], [14.0, 15.0]];
+        let builder = || {
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add(ATTRIBUTE_UV, &uvs)
+        };
+
+        let (iter, info) = builder().build().unwrap();
+        let data = iter.collect_vec();
+
+        let chunks = builder().build_chunked(45).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].0, data[..40]);
+        assert_eq!(chunks[0].1, info);
+        assert_eq!(chunks[0].2, 0..2);
+        assert_eq!(chunks[1].0, data[40..]);
+        assert_eq!(chunks[1].2, 2..3);
+
+        let chunks = builder().build_chunked(60).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].0, data);
+        assert_eq!(chunks[0].2, 0..3);
+
+        assert_eq!(
+            RuntimeVertexBuilder::new().build_chunked(64).unwrap_err(),
+            RuntimeVertexBuildError::NoAttributes,
+        );
+    }
+
+    #[test]
+    fn build_chunked_too_small() {
+        let positions = [[0.0f32; 3]; 2];
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .build_chunked(8)
+                .unwrap_err(),
+            RuntimeVertexBuildError::ChunkTooSmall {
+                max_bytes_per_chunk: 8,
+                stride: 12,
+            },
+        );
+    }
+
+    #[test]
+    fn add_struct() {
+        use crate::pipeline::graphics::vertex_input::RuntimeVertex;
+        use bytemuck::{Pod, Zeroable};
+
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, Default, Zeroable, Pod, RuntimeVertex)]
+        struct TestVertex {
+            #[format(R32G32B32_SFLOAT)]
+            position: [f32; 3],
+            #[format(R32_UINT)]
+            index: u32,
+        }
+
+        let vertices = [
+            TestVertex {
+                position: [1.0, 2.0, 3.0],
+                index: 4,
+            },
+            TestVertex {
+                position: [5.0, 6.0, 7.0],
+                index: 8,
+            },
+        ];
+        let weights = [9.0f32, 10.0];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add(VertexAttribute::new("weight", Format::R32_SFLOAT), &weights)
+            .add_struct(&TestVertex::attributes(), &vertices)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 20);
+        assert_eq!(info.offset_of("position"), Some(4));
+        assert_eq!(info.offset_of("index"), Some(16));
+
+        let data = iter.collect_vec();
+        assert_eq!(
+            bytemuck::cast_slice::<_, f32>(&data[..16]),
+            &[9.0, 1.0, 2.0, 3.0],
+        );
+        assert_eq!(bytemuck::cast_slice::<_, u32>(&data[16..20]), &[4]);
+        assert_eq!(
+            bytemuck::cast_slice::<_, f32>(&data[20..36]),
+            &[10.0, 5.0, 6.0, 7.0],
+        );
+        assert_eq!(bytemuck::cast_slice::<_, u32>(&data[36..]), &[8]);
+
+        let uvs = [[1.0f32, 2.0], [3.0, 4.0]];
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add_struct(&[ATTRIBUTE_UV], &uvs)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 8);
+    }
+
+    #[test]
+    fn add_struct_size_mismatch() {
+        let vertices = [[0.0f32; 6]; 2];
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_struct(&[ATTRIBUTE_POSITION, ATTRIBUTE_UV], &vertices)
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::StructSizeMismatch {
+                attributes_size: 20,
+                struct_size: 24,
+            },
+        );
+
+        // With explicit offsets, padding is allowed, but attributes must stay within the struct
+        // and must not overlap.
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add_struct(
+                &[ATTRIBUTE_POSITION, ATTRIBUTE_UV.with_offset(16)],
+                &vertices,
+            )
+            .build()
+            .unwrap();
+        assert_eq!(info.offset_of("uv"), Some(16));
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_struct(
+                    &[ATTRIBUTE_POSITION, ATTRIBUTE_UV.with_offset(20)],
+                    &vertices,
+                )
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::AttributeOutsideStruct {
+                attribute: "uv".to_owned(),
+                end: 28,
+                struct_size: 24,
+            },
+        );
+        assert!(matches!(
+            RuntimeVertexBuilder::new()
+                .add_struct(
+                    &[ATTRIBUTE_POSITION, ATTRIBUTE_UV.with_offset(8)],
+                    &vertices,
+                )
+                .build(),
+            Err(RuntimeVertexBuildError::OverlappingAttributes { .. }),
+        ));
+    }
+
+    #[test]
+    fn add_indexed() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0], [11.0, 12.0]];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add_indexed(ATTRIBUTE_POSITION, &positions, &[0, 1, 1])
+            .add_indexed(ATTRIBUTE_UV, &uvs, &[2, 0, 1])
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 20);
+        assert_eq!(
+            bytemuck::cast_slice::<_, f32>(&iter.collect_vec()),
+            &[1.0, 2.0, 3.0, 11.0, 12.0, 4.0, 5.0, 6.0, 7.0, 8.0, 4.0, 5.0, 6.0, 9.0, 10.0,],
+        );
+
+        assert!(matches!(
+            RuntimeVertexBuilder::new()
+                .add_indexed(ATTRIBUTE_POSITION, &positions, &[0, 1])
+                .add_indexed(ATTRIBUTE_UV, &uvs, &[2, 0, 1])
+                .build(),
+            Err(RuntimeVertexBuildError::MismatchedVertexCounts { .. }),
+        ));
+    }
+
+    #[test]
+    fn add_indexed_out_of_bounds() {
+        let positions = [[0.0f32; 3]; 2];
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_indexed(ATTRIBUTE_POSITION, &positions, &[0, 2, 3])
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::IndexOutOfBounds {
+                attribute: "position".to_owned(),
+                index: 2,
+                len: 2,
+            },
+        );
+    }
+
+    #[test]
+    fn add_field() {
+        struct Material {
+            color: [u8; 4],
+            roughness: f32,
+        }
+
+        struct Vertex {
+            position: [f32; 3],
+            material: Material,
+        }
+
+        let vertices = [
+            Vertex {
+                position: [1.0, 2.0, 3.0],
+                material: Material {
+                    color: [1, 2, 3, 4],
+                    roughness: 0.5,
+                },
+            },
+            Vertex {
+                position: [4.0, 5.0, 6.0],
+                material: Material {
+                    color: [5, 6, 7, 8],
+                    roughness: 0.25,
+                },
+            },
+        ];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add_field(ATTRIBUTE_POSITION, &vertices, |vertex| &vertex.position)
+            .add_field(
+                VertexAttribute::new("color", Format::R8G8B8A8_UNORM),
+                &vertices,
+                |vertex| &vertex.material.color,
+            )
+            .add_field(
+                VertexAttribute::new("roughness", Format::R32_SFLOAT),
+                &vertices,
+                |vertex| &vertex.material.roughness,
+            )
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 20);
+
+        let mut expected = Vec::new();
+        for vertex in &vertices {
+            expected.extend_from_slice(bytemuck::bytes_of(&vertex.position));
+            expected.extend_from_slice(&vertex.material.color);
+            expected.extend_from_slice(bytemuck::bytes_of(&vertex.material.roughness));
+        }
+        assert_interleaved_eq(iter, &expected);
+    }
+
+    #[test]
+    fn add_f16() {
+        let uvs = [[0.5f32, 1.0], [-2.0, 65504.0]];
+        let flags = [1u8, 2];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add_f16(VertexAttribute::new("uv", Format::R16G16_SFLOAT), &uvs)
+            .add(VertexAttribute::new("flags", Format::R8_UINT), &flags)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 5);
+
+        let data = iter.collect_vec();
+        let half = |bytes: &[u8]| half::f16::from_ne_bytes([bytes[0], bytes[1]]).to_f32();
+        assert_eq!(half(&data[0..2]), 0.5);
+        assert_eq!(half(&data[2..4]), 1.0);
+        assert_eq!(data[4], 1);
+        assert_eq!(half(&data[5..7]), -2.0);
+        assert_eq!(half(&data[7..9]), 65504.0);
+    }
+
+    #[test]
+    fn add_f16_wrong_format() {
+        let uvs = [[0.0f32; 2]; 2];
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_f16(ATTRIBUTE_UV, &uvs)
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::NotHalfFloatFormat {
+                attribute: "uv".to_owned(),
+                format: Format::R32G32_SFLOAT,
+            },
+        );
+    }
+
+    #[test]
+    fn add_f16_component_mismatch() {
+        let uvs = [[0.0f32; 3]; 2];
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_f16(VertexAttribute::new("uv", Format::R16G16_SFLOAT), &uvs)
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::FormatSizeMismatch {
+                attribute: "uv".to_owned(),
+                expected: 4,
+                provided: 6,
+            },
+        );
+    }
+
+    #[test]
+    fn add_swizzled() {
+        let colors = [[1u8, 2, 3, 4], [5, 6, 7, 8]];
+        let bgra = ComponentMapping {
+            r: ComponentSwizzle::Blue,
+            g: ComponentSwizzle::Green,
+            b: ComponentSwizzle::Red,
+            a: ComponentSwizzle::Alpha,
+        };
+
+        let (iter, _) = RuntimeVertexBuilder::new()
+            .add_swizzled(
+                VertexAttribute::new("color", Format::B8G8R8A8_UNORM),
+                &colors,
+                bgra,
+            )
+            .build()
+            .unwrap();
+        assert_eq!(iter.collect_vec(), [3, 2, 1, 4, 7, 6, 5, 8]);
+
+        let positions = [[1.0f32, 2.0, 3.0]];
+        let mapping = ComponentMapping {
+            r: ComponentSwizzle::One,
+            g: ComponentSwizzle::Zero,
+            b: ComponentSwizzle::Red,
+            a: ComponentSwizzle::Green,
+        };
+
+        let (iter, _) = RuntimeVertexBuilder::new()
+            .add_swizzled(ATTRIBUTE_POSITION, &positions, mapping)
+            .build()
+            .unwrap();
+        assert_eq!(
+            bytemuck::cast_slice::<_, f32>(&iter.collect_vec()),
+            &[1.0, 0.0, 1.0],
+        );
+
+        let (iter, _) = RuntimeVertexBuilder::new()
+            .add_swizzled(
+                VertexAttribute::new("normal", Format::R8G8_SNORM),
+                &[[0u8; 2]],
+                mapping,
+            )
+            .build()
+            .unwrap();
+        assert_eq!(iter.collect_vec(), [0x7f, 0]);
+    }
+
+    #[test]
+    fn add_swizzled_errors() {
+        let uvs = [[0.0f32; 2]];
+        let mapping = ComponentMapping {
+            r: ComponentSwizzle::Blue,
+            ..Default::default()
+        };
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_swizzled(ATTRIBUTE_UV, &uvs, mapping)
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::MissingSwizzleComponent {
+                attribute: "uv".to_owned(),
+                component: 2,
+                format: Format::R32G32_SFLOAT,
+            },
+        );
+
+        assert_eq!(
+            RuntimeVertexBuilder::new()
+                .add_swizzled(
+                    VertexAttribute::new("normal", Format::A2B10G10R10_SNORM_PACK32),
+                    &[0u32],
+                    ComponentMapping::identity(),
+                )
+                .build()
+                .unwrap_err(),
+            RuntimeVertexBuildError::PackedFormatSwizzle {
+                attribute: "normal".to_owned(),
+                format: Format::A2B10G10R10_SNORM_PACK32,
+            },
+        );
+    }
+
+    #[test]
+    fn zero_sized_attribute() {
+        use bytemuck::{Pod, Zeroable};
+
+        #[derive(Clone, Copy, Zeroable, Pod)]
+        #[repr(C)]
+        struct Unit;
+
+        let units = [Unit; 4];
+        let positions = [[0.0f32; 3]; 4];
+
+        let result = RuntimeVertexBuilder::new()
+            .add(VertexAttribute::new("unit", Format::R8_UNORM), &units)
+            .add(ATTRIBUTE_POSITION, &positions)
+            .build();
+        assert!(matches!(
+            result,
+            Err(RuntimeVertexBuildError::ZeroSizedAttribute { attribute }) if attribute == "unit",
+        ));
+    }
+
+    #[test]
+    fn every_vertex_attribute_format() {
+        let formats = Format::vertex_attribute_formats();
+        assert!(formats.contains(&Format::R32G32B32_SFLOAT));
+        assert!(formats.contains(&Format::A2B10G10R10_UNORM_PACK32));
+        assert!(!formats.contains(&Format::D32_SFLOAT));
+        assert!(!formats.contains(&Format::BC1_RGB_UNORM_BLOCK));
+
+        for &format in formats {
+            let block_size = format.block_size().unwrap() as usize;
+            let data = vec![0; block_size * 3];
+
+            let (iter, info) = RuntimeVertexBuilder::new()
+                .add_bytes(VertexAttribute::new("attribute", format), &data, block_size)
+                .build()
+                .unwrap();
+            assert_eq!(info.stride as usize, block_size);
+            assert_eq!(iter.len(), data.len());
+        }
+    }
+
+    #[test]
+    fn build_separate() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let offsets = [[7.0f32, 8.0]];
+
+        let buffers = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add_instanced(ATTRIBUTE_UV.with_offset(4), &offsets, 1)
+            .build_separate()
+            .unwrap();
+        assert_eq!(buffers.len(), 2);
+
+        let (bytes, info) = &buffers[0];
+        assert_eq!(bytes.as_slice(), bytemuck::cast_slice::<_, u8>(&positions));
+        assert_eq!(info.stride, 12);
+        assert_eq!(info.input_rate, VertexInputRate::Vertex);
+        assert_eq!(info.members[0].0, "position");
+
+        let (bytes, info) = &buffers[1];
+        assert_eq!(bytes.as_slice(), bytemuck::cast_slice::<_, u8>(&offsets));
+        assert_eq!(info.stride, 8);
+        assert_eq!(info.input_rate, VertexInputRate::Instance { divisor: 1 });
+        assert_eq!(info.members[0].1.offset, 0);
+    }
+
+    #[test]
+    fn owned() {
+        fn generate(num_vertices: usize) -> (OwnedRuntimeVertexIter, VertexBufferInfo) {
+            let positions: Vec<[f32; 3]> = (0..num_vertices).map(|i| [i as f32; 3]).collect();
+            let uvs: Vec<[f32; 2]> = (0..num_vertices).map(|i| [i as f32 * 2.0; 2]).collect();
+
+            OwnedRuntimeVertexBuilder::new()
+                .add_owned(ATTRIBUTE_POSITION, positions)
+                .add_owned(ATTRIBUTE_UV, uvs)
+                .build()
+                .unwrap()
+        }
+
+        let (iter, info) = generate(2);
+        assert_eq!(info.stride, 20);
+
+        let bytes: Vec<u8> = iter.collect();
+        let floats: &[f32] = bytemuck::cast_slice(&bytes);
+        assert_eq!(floats, &[0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0],);
+    }
+}
@@ -0,0 +1,643 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::cpu_access::WriteLockError, format::Format, memory::allocator::AllocationCreationError,
+};
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Error as FmtError, Formatter},
+};
+
+/// Error that can happen when building interleaved vertex data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuntimeVertexBuildError {
+    /// No attributes were added to the builder.
+    NoAttributes,
+
+    /// Attributes with different input rates were added, but only a single vertex buffer was
+    /// requested.
+    MixedInputRates,
+
+    /// Attributes were added to a binding other than 0 with
+    /// [`RuntimeVertexBuilder::add_to_binding`](super::RuntimeVertexBuilder::add_to_binding), but
+    /// the data wasn't built with
+    /// [`RuntimeVertexBuilder::build_multi`](super::RuntimeVertexBuilder::build_multi).
+    MultipleBindings {
+        /// The binding of the first attribute that was added to a binding other than 0.
+        binding: u32,
+    },
+
+    /// The attributes do not all provide data for the same number of vertices.
+    MismatchedVertexCounts {
+        /// The name of each attribute, and the number of vertices it provides data for.
+        counts: Vec<(String, usize)>,
+    },
+
+    /// The data of an attribute is empty, so no vertices would be built.
+    ///
+    /// To intentionally build empty vertex data, use
+    /// [`RuntimeVertexBuilder::build_unchecked`](super::RuntimeVertexBuilder::build_unchecked).
+    EmptySlice {
+        /// Name of the attribute.
+        attribute: String,
+    },
+
+    /// Multiple attributes were added with the same name. Names are compared case-sensitively,
+    /// like the names of a shader interface.
+    DuplicateAttribute {
+        /// Name of the attributes.
+        attribute: String,
+    },
+
+    /// Two attributes with the same input rate occupy overlapping bytes of a vertex.
+    OverlappingAttributes {
+        /// Name of the first attribute.
+        first: String,
+        /// Name of the second attribute.
+        second: String,
+    },
+
+    /// [`RuntimeVertexBuilder::extend`](super::RuntimeVertexBuilder::extend) was called with the
+    /// name of an attribute that was not added.
+    UnknownAttribute {
+        /// Name of the attribute.
+        attribute: String,
+    },
+
+    /// [`RuntimeVertexBuilder::extend`](super::RuntimeVertexBuilder::extend) was called with data
+    /// whose elements have a different size than the data that the attribute was added with.
+    ElementSizeMismatch {
+        /// Name of the attribute.
+        attribute: String,
+        /// The size of an element of the data that the attribute was added with.
+        expected: usize,
+        /// The size of an element of the provided data.
+        provided: usize,
+    },
+
+    /// [`RuntimeVertexBuilder::append_interleaved`](super::RuntimeVertexBuilder::append_interleaved)
+    /// was called with a layout that doesn't match the attributes of the builder.
+    LayoutMismatch {
+        /// Name of an attribute that is laid out differently or is missing from one of the
+        /// layouts, or `None` if the strides differ or the builder has no attributes with the
+        /// input rate of the layout.
+        attribute: Option<String>,
+    },
+
+    /// [`RuntimeVertexBuilder::append_interleaved`](super::RuntimeVertexBuilder::append_interleaved)
+    /// was called with data that doesn't match its layout.
+    InvalidInterleavedData(DeinterleaveError),
+
+    /// Only some of the attributes were assigned a location with
+    /// [`RuntimeVertexBuilder::add_at_location`](super::RuntimeVertexBuilder::add_at_location).
+    MixedLocationAssignment,
+
+    /// The shader locations of two attributes overlap.
+    LocationCollision {
+        /// The first location that is taken up by both attributes.
+        location: u32,
+    },
+
+    /// The stride requested with
+    /// [`RuntimeVertexBuilder::with_stride`](super::RuntimeVertexBuilder::with_stride) is smaller
+    /// than the attributes of a vertex.
+    StrideTooSmall {
+        /// Name of the attribute that ends furthest past the stride.
+        attribute: String,
+        /// The requested stride.
+        stride: u32,
+        /// The number of bytes that the attributes of a vertex occupy.
+        required: u32,
+    },
+
+    /// An attribute was added with a format that has no block size.
+    FormatHasNoBlockSize {
+        /// Name of the attribute.
+        attribute: String,
+        /// The format of the attribute.
+        format: Format,
+    },
+
+    /// An attribute was added with a format that can't be used for vertex attributes, such as a
+    /// compressed format.
+    UnsupportedFormat {
+        /// Name of the attribute.
+        attribute: String,
+        /// The format of the attribute.
+        format: Format,
+    },
+
+    /// An attribute was added without a number of elements, and the size of an element of its
+    /// data is not a multiple of the size of its format.
+    StrideFormatMismatch {
+        /// Name of the attribute.
+        attribute: String,
+        /// The size of an element that has the components of the format, with as many elements
+        /// as fit in the provided size.
+        expected: usize,
+        /// The size of an element of the provided data.
+        provided: usize,
+    },
+
+    /// An attribute was added with a number of elements, with a packed format, or with
+    /// [`RuntimeVertexBuilder::add_f16`](super::RuntimeVertexBuilder::add_f16), and the size of an
+    /// element of its data is not the size of its format multiplied by the number of elements.
+    FormatSizeMismatch {
+        /// Name of the attribute.
+        attribute: String,
+        /// The size of the format multiplied by the number of elements.
+        expected: usize,
+        /// The size of an element of the provided data.
+        provided: usize,
+    },
+
+    /// The elements of the data added with
+    /// [`RuntimeVertexBuilder::add_matrix`](super::RuntimeVertexBuilder::add_matrix) don't have the
+    /// size of a matrix with the given columns.
+    MatrixSizeMismatch {
+        /// The base name of the columns.
+        attribute: String,
+        /// The size of a matrix with the given columns.
+        matrix_size: usize,
+        /// The size of an element of the provided data.
+        element_size: usize,
+    },
+
+    /// An attribute was added with bytes whose length is not a multiple of the size of an
+    /// element.
+    DataSizeMismatch {
+        /// Name of the attribute.
+        attribute: String,
+        /// The length of the data in bytes.
+        data_len: usize,
+        /// The size of an element.
+        element_size: usize,
+    },
+
+    /// An attribute was added with data whose elements have a size of zero.
+    ZeroSizedAttribute {
+        /// Name of the attribute.
+        attribute: String,
+    },
+
+    /// An attribute that was added with
+    /// [`RuntimeVertexBuilder::add_struct`](super::RuntimeVertexBuilder::add_struct) extends past
+    /// the end of the struct.
+    AttributeOutsideStruct {
+        /// Name of the attribute.
+        attribute: String,
+        /// The offset of the end of the attribute within the struct.
+        end: usize,
+        /// The size of the struct.
+        struct_size: usize,
+    },
+
+    /// The attributes that were added with
+    /// [`RuntimeVertexBuilder::add_struct`](super::RuntimeVertexBuilder::add_struct) have no
+    /// explicit offsets, and their sizes don't add up to the size of the struct.
+    StructSizeMismatch {
+        /// The sum of the sizes of the attributes.
+        attributes_size: usize,
+        /// The size of the struct.
+        struct_size: usize,
+    },
+
+    /// An attribute was added with
+    /// [`RuntimeVertexBuilder::add_strided`](super::RuntimeVertexBuilder::add_strided) with a
+    /// stride that is smaller than its elements, or with data that doesn't end with a whole
+    /// element.
+    SourceStrideMismatch {
+        /// Name of the attribute.
+        attribute: String,
+        /// The provided stride.
+        stride: usize,
+        /// The size of an element of the attribute.
+        element_size: usize,
+        /// The length of the provided data.
+        data_len: usize,
+    },
+
+    /// An attribute with an integer format is not aligned to the size of its components, and
+    /// alignment is checked because of
+    /// [`RuntimeVertexBuilder::with_integer_alignment`](super::RuntimeVertexBuilder::with_integer_alignment).
+    MisalignedAttribute {
+        /// Name of the attribute.
+        attribute: String,
+        /// The offset of the attribute.
+        offset: usize,
+        /// The alignment that the offset must have.
+        required: usize,
+    },
+
+    /// An attribute was added with
+    /// [`RuntimeVertexBuilder::add_indexed`](super::RuntimeVertexBuilder::add_indexed) with an
+    /// index that is out of bounds for its data.
+    IndexOutOfBounds {
+        /// Name of the attribute.
+        attribute: String,
+        /// The first index that is out of bounds.
+        index: u32,
+        /// The number of elements of the data.
+        len: usize,
+    },
+
+    /// An attribute was added with
+    /// [`RuntimeVertexBuilder::add_f16`](super::RuntimeVertexBuilder::add_f16) with a format whose
+    /// components are not all 16-bit floating point numbers.
+    NotHalfFloatFormat {
+        /// Name of the attribute.
+        attribute: String,
+        /// The format of the attribute.
+        format: Format,
+    },
+
+    /// An attribute with a packed format was added with
+    /// [`RuntimeVertexBuilder::add_swizzled`](super::RuntimeVertexBuilder::add_swizzled), whose
+    /// components can't be swizzled.
+    PackedFormatSwizzle {
+        /// Name of the attribute.
+        attribute: String,
+        /// The format of the attribute.
+        format: Format,
+    },
+
+    /// An attribute was added with
+    /// [`RuntimeVertexBuilder::add_swizzled`](super::RuntimeVertexBuilder::add_swizzled), with a
+    /// swizzle that selects a component that its format doesn't have.
+    MissingSwizzleComponent {
+        /// Name of the attribute.
+        attribute: String,
+        /// The index of the selected component.
+        component: usize,
+        /// The format of the attribute.
+        format: Format,
+    },
+
+    /// The maximum size of a chunk that was passed to
+    /// [`RuntimeVertexBuilder::build_chunked`](super::RuntimeVertexBuilder::build_chunked) is
+    /// smaller than a vertex.
+    ChunkTooSmall {
+        /// The maximum number of bytes of a chunk.
+        max_bytes_per_chunk: usize,
+        /// The stride of a vertex.
+        stride: usize,
+    },
+}
+
+impl Error for RuntimeVertexBuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidInterleavedData(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for RuntimeVertexBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::NoAttributes => write!(f, "no attributes were added to the builder"),
+            Self::MixedInputRates => write!(
+                f,
+                "attributes with different input rates can't be placed in the same vertex buffer",
+            ),
+            Self::MultipleBindings { binding } => write!(
+                f,
+                "attributes were added to binding {}, so the data must be built with \
+                `build_multi`",
+                binding,
+            ),
+            Self::MismatchedVertexCounts { counts } => {
+                write!(
+                    f,
+                    "the attributes provide data for different numbers of vertices:"
+                )?;
+
+                for (i, (name, count)) in counts.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{}`{}`: {}", separator, name, count)?;
+                }
+
+                Ok(())
+            }
+            Self::EmptySlice { attribute } => {
+                write!(f, "the data of attribute `{}` is empty", attribute)
+            }
+            Self::DuplicateAttribute { attribute } => write!(
+                f,
+                "multiple attributes were added with the name `{}`",
+                attribute,
+            ),
+            Self::OverlappingAttributes { first, second } => write!(
+                f,
+                "attributes `{}` and `{}` overlap within a vertex",
+                first, second,
+            ),
+            Self::UnknownAttribute { attribute } => {
+                write!(f, "no attribute named `{}` was added", attribute)
+            }
+            Self::ElementSizeMismatch {
+                attribute,
+                expected,
+                provided,
+            } => write!(
+                f,
+                "the elements of attribute `{}` are {} bytes large, but the provided elements \
+                are {} bytes large",
+                attribute, expected, provided,
+            ),
+            Self::LayoutMismatch { attribute: None } => write!(
+                f,
+                "the stride or input rate of the interleaved data doesn't match the attributes",
+            ),
+            Self::LayoutMismatch {
+                attribute: Some(attribute),
+            } => write!(
+                f,
+                "attribute `{}` is laid out differently in the interleaved data, or is missing \
+                from one of the layouts",
+                attribute,
+            ),
+            Self::InvalidInterleavedData(_) => {
+                write!(f, "the interleaved data doesn't match its layout")
+            }
+            Self::MixedLocationAssignment => write!(
+                f,
+                "only some of the attributes were assigned a location, which is ambiguous",
+            ),
+            Self::LocationCollision { location } => write!(
+                f,
+                "multiple attributes were assigned to location {}",
+                location,
+            ),
+            Self::StrideTooSmall {
+                attribute,
+                stride,
+                required,
+            } => write!(
+                f,
+                "the requested stride is {} bytes, but attribute `{}` ends at byte {}",
+                stride, attribute, required,
+            ),
+            Self::FormatHasNoBlockSize { attribute, format } => write!(
+                f,
+                "format {:?} of attribute `{}` has no block size",
+                format, attribute,
+            ),
+            Self::UnsupportedFormat { attribute, format } => write!(
+                f,
+                "format {:?} of attribute `{}` can't be used for vertex attributes",
+                format, attribute,
+            ),
+            Self::StrideFormatMismatch {
+                attribute,
+                expected,
+                provided,
+            } => write!(
+                f,
+                "the elements of the data of attribute `{}` are {} bytes, but its format needs \
+                {} bytes",
+                attribute, provided, expected,
+            ),
+            Self::FormatSizeMismatch {
+                attribute,
+                expected,
+                provided,
+            } => write!(
+                f,
+                "the elements of the data of attribute `{}` are {} bytes, but its format and \
+                number of elements need {} bytes",
+                attribute, provided, expected,
+            ),
+            Self::MatrixSizeMismatch {
+                attribute,
+                matrix_size,
+                element_size,
+            } => write!(
+                f,
+                "the elements of the data of matrix `{}` are {} bytes, but its columns need {} \
+                bytes",
+                attribute, element_size, matrix_size,
+            ),
+            Self::DataSizeMismatch {
+                attribute,
+                data_len,
+                element_size,
+            } => write!(
+                f,
+                "the data of attribute `{}` is {} bytes, which is not a multiple of its element \
+                size of {} bytes",
+                attribute, data_len, element_size,
+            ),
+            Self::ZeroSizedAttribute { attribute } => write!(
+                f,
+                "the elements of the data of attribute `{}` have a size of zero",
+                attribute,
+            ),
+            Self::AttributeOutsideStruct {
+                attribute,
+                end,
+                struct_size,
+            } => write!(
+                f,
+                "attribute `{}` ends at byte {}, past the end of the struct of {} bytes",
+                attribute, end, struct_size,
+            ),
+            Self::StructSizeMismatch {
+                attributes_size,
+                struct_size,
+            } => write!(
+                f,
+                "the sizes of the attributes add up to {} bytes, but the struct is {} bytes",
+                attributes_size, struct_size,
+            ),
+            Self::SourceStrideMismatch {
+                attribute,
+                stride,
+                element_size,
+                data_len,
+            } => write!(
+                f,
+                "the data of attribute `{}` is {} bytes with a stride of {}, which doesn't hold \
+                a whole number of elements of {} bytes",
+                attribute, data_len, stride, element_size,
+            ),
+            Self::MisalignedAttribute {
+                attribute,
+                offset,
+                required,
+            } => write!(
+                f,
+                "attribute `{}` has an offset of {}, which is not a multiple of the size of its \
+                components, {}",
+                attribute, offset, required,
+            ),
+            Self::IndexOutOfBounds {
+                attribute,
+                index,
+                len,
+            } => write!(
+                f,
+                "index {} of attribute `{}` is out of bounds for {} elements",
+                index, attribute, len,
+            ),
+            Self::NotHalfFloatFormat { attribute, format } => write!(
+                f,
+                "format {:?} of attribute `{}` is not a 16-bit floating point format",
+                format, attribute,
+            ),
+            Self::PackedFormatSwizzle { attribute, format } => write!(
+                f,
+                "format {:?} of attribute `{}` is packed, so its components can't be swizzled",
+                format, attribute,
+            ),
+            Self::MissingSwizzleComponent {
+                attribute,
+                component,
+                format,
+            } => write!(
+                f,
+                "the swizzle selects component {} of attribute `{}`, but its format {:?} only \
+                has {} components",
+                component,
+                attribute,
+                format,
+                format.component_count(),
+            ),
+            Self::ChunkTooSmall {
+                max_bytes_per_chunk,
+                stride,
+            } => write!(
+                f,
+                "a chunk of {} bytes can't contain a vertex of {} bytes",
+                max_bytes_per_chunk, stride,
+            ),
+        }
+    }
+}
+
+/// Error that can happen when writing interleaved vertex data into a slice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuntimeVertexWriteError {
+    /// The destination slice is too small to hold the data.
+    DestinationTooSmall {
+        /// The number of bytes of the data.
+        required: usize,
+        /// The number of bytes of the destination slice.
+        provided: usize,
+    },
+}
+
+impl Error for RuntimeVertexWriteError {}
+
+impl Display for RuntimeVertexWriteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::DestinationTooSmall { required, provided } => write!(
+                f,
+                "the destination is {} bytes long, but the data is {} bytes long",
+                provided, required,
+            ),
+        }
+    }
+}
+
+/// Error that can happen when writing interleaved vertex data into a new buffer with
+/// [`RuntimeVertexIter::into_buffer`](super::RuntimeVertexIter::into_buffer).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuntimeVertexBufferError {
+    /// There is no data left, and a buffer can't be empty.
+    NoData,
+
+    /// Allocating the buffer failed.
+    AllocError(AllocationCreationError),
+
+    /// The buffer couldn't be locked for writing.
+    WriteLockError(WriteLockError),
+}
+
+impl Error for RuntimeVertexBufferError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::AllocError(err) => Some(err),
+            Self::WriteLockError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for RuntimeVertexBufferError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::NoData => write!(f, "there is no data left to write into a buffer"),
+            Self::AllocError(err) => Display::fmt(err, f),
+            Self::WriteLockError(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl From<AllocationCreationError> for RuntimeVertexBufferError {
+    #[inline]
+    fn from(err: AllocationCreationError) -> Self {
+        Self::AllocError(err)
+    }
+}
+
+impl From<WriteLockError> for RuntimeVertexBufferError {
+    #[inline]
+    fn from(err: WriteLockError) -> Self {
+        Self::WriteLockError(err)
+    }
+}
+
+/// Error that can happen when interleaved vertex data doesn't match its
+/// [`VertexBufferInfo`](super::VertexBufferInfo), as checked by
+/// [`deinterleave`](super::deinterleave) and
+/// [`BorrowedVertexInput::new`](super::BorrowedVertexInput::new).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeinterleaveError {
+    /// The length of the data is not a multiple of the stride.
+    InvalidLength {
+        /// The number of bytes of the data.
+        length: usize,
+        /// The stride of a vertex.
+        stride: u32,
+    },
+
+    /// A member extends past the end of a vertex.
+    MemberOutOfBounds {
+        /// Name of the member.
+        attribute: String,
+    },
+}
+
+impl Error for DeinterleaveError {}
+
+impl From<DeinterleaveError> for RuntimeVertexBuildError {
+    #[inline]
+    fn from(err: DeinterleaveError) -> Self {
+        Self::InvalidInterleavedData(err)
+    }
+}
+
+impl Display for DeinterleaveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::InvalidLength { length, stride } => write!(
+                f,
+                "the data is {} bytes long, which is not a multiple of the stride {}",
+                length, stride,
+            ),
+            Self::MemberOutOfBounds { attribute } => {
+                write!(f, "member `{}` extends past the end of a vertex", attribute,)
+            }
+        }
+    }
+}
@@ -0,0 +1,446 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    builder::{RuntimeVertexBuilder, RuntimeVertexMember},
+    error::{RuntimeVertexBuildError, RuntimeVertexWriteError},
+    VertexAttribute, VertexBufferInfo,
+};
+use crate::pipeline::graphics::vertex_input::VertexInputRate;
+use bytemuck::Pod;
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Error as FmtError, Formatter},
+    io::{self, Read},
+    mem,
+    ops::Range,
+};
+
+/// Builds interleaved vertex data whose attributes are generated for each vertex by a closure,
+/// instead of being read from slices.
+///
+/// The layout is the same as a [`RuntimeVertexBuilder`] with the same attributes would produce,
+/// but the data is generated by [`GeneratedVertexIter`] one vertex at a time, so it never has to
+/// be stored in full. This is useful for large procedural meshes.
+///
+/// All attributes have a per-vertex input rate, and the data is produced in the byte order of
+/// the host.
+pub struct GeneratedVertexBuilder<'g> {
+    // Holds the attributes, without any data, to lay them out like `RuntimeVertexBuilder` does.
+    layout: RuntimeVertexBuilder<'static>,
+    generators: Vec<Box<dyn Fn(usize, &mut [u8]) + 'g>>,
+    vertex_count: usize,
+}
+
+impl<'g> GeneratedVertexBuilder<'g> {
+    /// Creates a new `GeneratedVertexBuilder` for `vertex_count` vertices, without any
+    /// attributes.
+    #[inline]
+    pub fn new(vertex_count: usize) -> Self {
+        GeneratedVertexBuilder {
+            layout: RuntimeVertexBuilder::new(),
+            generators: Vec::new(),
+            vertex_count,
+        }
+    }
+
+    /// Adds an attribute whose data for the vertex with index `i` is `generator(i)`.
+    ///
+    /// The attribute is placed and checked like [`RuntimeVertexBuilder::add`] places and checks
+    /// it.
+    pub fn add<T: Pod>(
+        mut self,
+        attribute: VertexAttribute,
+        generator: impl Fn(usize) -> T + 'g,
+    ) -> Self {
+        let num_members = self.layout.members.len();
+        self.layout = self.layout.add_member(
+            attribute,
+            Cow::Borrowed(&[]),
+            mem::size_of::<T>(),
+            VertexInputRate::Vertex,
+            1,
+        );
+
+        // Attributes that fail the checks of `add_member` are not added.
+        if self.layout.members.len() > num_members {
+            self.generators.push(Box::new(move |index, dst| {
+                dst.copy_from_slice(bytemuck::bytes_of(&generator(index)));
+            }));
+        }
+
+        self
+    }
+
+    /// Sets the stride of a vertex, like [`RuntimeVertexBuilder::with_stride`].
+    #[inline]
+    pub fn with_stride(mut self, stride: u32) -> Self {
+        self.layout = self.layout.with_stride(stride);
+        self
+    }
+
+    /// Returns an iterator that generates the interleaved vertex data, and a description of its
+    /// layout.
+    pub fn build(
+        self,
+    ) -> Result<(GeneratedVertexIter<'g>, VertexBufferInfo), RuntimeVertexBuildError> {
+        let layout = self.layout;
+        layout.validate_added()?;
+
+        if layout.members.is_empty() {
+            return Err(RuntimeVertexBuildError::NoAttributes);
+        }
+
+        layout.validate_unique_names()?;
+        layout.validate_layout()?;
+
+        let stride = layout.stride_of(VertexInputRate::Vertex);
+        let member_ranges = layout
+            .members
+            .iter()
+            .map(RuntimeVertexMember::byte_range)
+            .collect();
+        let info = VertexBufferInfo {
+            members: layout
+                .members
+                .into_iter()
+                .map(|member| (member.name, member.info))
+                .collect(),
+            stride: stride as u32,
+            input_rate: VertexInputRate::Vertex,
+        };
+        let iter = GeneratedVertexIter {
+            generators: self.generators,
+            member_ranges,
+            vertex: vec![0; stride],
+            vertex_index: 0,
+            vertex_count: self.vertex_count,
+            position: stride,
+        };
+
+        Ok((iter, info))
+    }
+}
+
+impl Debug for GeneratedVertexBuilder<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.debug_struct("GeneratedVertexBuilder")
+            .field("layout", &self.layout)
+            .field("vertex_count", &self.vertex_count)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Iterator over the bytes of interleaved vertex data produced by a [`GeneratedVertexBuilder`].
+///
+/// The attributes of a vertex are generated when the first byte of the vertex is read. Bytes
+/// between the attributes are 0. Like [`RuntimeVertexIter`](super::RuntimeVertexIter), the data can
+/// also be read with [`Read`] or written directly into a slice, which copies whole vertices at
+/// once.
+pub struct GeneratedVertexIter<'g> {
+    generators: Vec<Box<dyn Fn(usize, &mut [u8]) + 'g>>,
+    // The byte ranges of the members within a vertex, in the same order as the generators.
+    member_ranges: Vec<Range<usize>>,
+    // The most recently generated vertex.
+    vertex: Vec<u8>,
+    // The index of the next vertex to generate.
+    vertex_index: usize,
+    vertex_count: usize,
+    // The position of the next byte to return within `vertex`.
+    position: usize,
+}
+
+impl<'g> GeneratedVertexIter<'g> {
+    /// Writes the remaining interleaved data directly into `dst`, and returns the number of bytes
+    /// that were written.
+    ///
+    /// Whole vertices are generated in place in `dst`, without going through an intermediate
+    /// buffer.
+    pub fn write_to_slice(mut self, dst: &mut [u8]) -> Result<usize, RuntimeVertexWriteError> {
+        let len = self.len();
+
+        if dst.len() < len {
+            return Err(RuntimeVertexWriteError::DestinationTooSmall {
+                required: len,
+                provided: dst.len(),
+            });
+        }
+
+        let mut written = 0;
+
+        while written < len {
+            written += self.copy_vertex(&mut dst[written..len]);
+        }
+
+        Ok(written)
+    }
+
+    /// Collects the remaining interleaved data into a `Vec`.
+    ///
+    /// Unlike `collect::<Vec<u8>>()`, this allocates the whole `Vec` up front and generates the
+    /// vertices directly into it.
+    pub fn collect_vec(mut self) -> Vec<u8> {
+        let len = self.len();
+        let mut data = vec![0; len];
+        let mut written = 0;
+
+        while written < len {
+            written += self.copy_vertex(&mut data[written..]);
+        }
+
+        data
+    }
+
+    // Copies the bytes of the current vertex, starting at the current position, into `dst`. If
+    // the current vertex has been read completely, the next one is generated first, directly into
+    // `dst` if it fits. Returns the number of bytes that were copied.
+    fn copy_vertex(&mut self, dst: &mut [u8]) -> usize {
+        let stride = self.vertex.len();
+
+        if self.position == stride {
+            if self.vertex_index == self.vertex_count || stride == 0 {
+                return 0;
+            }
+
+            if dst.len() >= stride {
+                let dst = &mut dst[..stride];
+                dst.fill(0);
+                generate_vertex(
+                    &self.generators,
+                    &self.member_ranges,
+                    self.vertex_index,
+                    dst,
+                );
+                self.vertex_index += 1;
+
+                return stride;
+            }
+
+            self.generate_next();
+        }
+
+        let len = (stride - self.position).min(dst.len());
+        dst[..len].copy_from_slice(&self.vertex[self.position..self.position + len]);
+        self.position += len;
+
+        len
+    }
+
+    // Generates the next vertex into `vertex`, and starts reading it from the beginning.
+    fn generate_next(&mut self) {
+        generate_vertex(
+            &self.generators,
+            &self.member_ranges,
+            self.vertex_index,
+            &mut self.vertex,
+        );
+        self.vertex_index += 1;
+        self.position = 0;
+    }
+}
+
+// Writes the members of the vertex with the given index into `dst`, leaving the bytes between
+// them untouched.
+pub(super) fn generate_vertex(
+    generators: &[Box<dyn Fn(usize, &mut [u8]) + '_>],
+    member_ranges: &[Range<usize>],
+    index: usize,
+    dst: &mut [u8],
+) {
+    for (generator, range) in generators.iter().zip(member_ranges) {
+        generator(index, &mut dst[range.clone()]);
+    }
+}
+
+impl<'g> Iterator for GeneratedVertexIter<'g> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        let mut byte = [0];
+
+        match self.read(&mut byte) {
+            Ok(1) => Some(byte[0]),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len();
+
+        (remaining, Some(remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<u8> {
+        let stride = self.vertex.len();
+        let n = n.min(self.len());
+        let left_in_vertex = stride - self.position;
+
+        if n < left_in_vertex {
+            self.position += n;
+        } else if let Some(skipped_vertices) = (n - left_in_vertex).checked_div(stride) {
+            // Vertices that are skipped entirely are never generated.
+            let offset = (n - left_in_vertex) % stride;
+            self.vertex_index += skipped_vertices;
+            self.position = stride;
+
+            if offset != 0 {
+                self.generate_next();
+                self.position = offset;
+            }
+        }
+
+        self.next()
+    }
+}
+
+impl<'g> Read for GeneratedVertexIter<'g> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.len());
+        let mut written = 0;
+
+        while written < len {
+            written += self.copy_vertex(&mut buf[written..len]);
+        }
+
+        Ok(written)
+    }
+}
+
+impl<'g> ExactSizeIterator for GeneratedVertexIter<'g> {
+    #[inline]
+    fn len(&self) -> usize {
+        let stride = self.vertex.len();
+
+        (self.vertex_count - self.vertex_index) * stride + (stride - self.position)
+    }
+}
+
+impl Debug for GeneratedVertexIter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.debug_struct("GeneratedVertexIter")
+            .field("member_ranges", &self.member_ranges)
+            .field("vertex_index", &self.vertex_index)
+            .field("vertex_count", &self.vertex_count)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeneratedVertexBuilder;
+    use crate::pipeline::graphics::vertex_input::{
+        runtime::tests::{ATTRIBUTE_POSITION, ATTRIBUTE_UV},
+        RuntimeVertexBuildError, RuntimeVertexBuilder, RuntimeVertexWriteError,
+    };
+    use std::io::Read;
+
+    #[test]
+    fn generated_vertices() {
+        let position = |index: usize| [index as f32, 0.0, 1.0];
+        let uv = |index: usize| [0.5f32, index as f32];
+
+        let (iter, info) = GeneratedVertexBuilder::new(3)
+            .add(ATTRIBUTE_POSITION, position)
+            .add(ATTRIBUTE_UV, uv)
+            .with_stride(24)
+            .build()
+            .unwrap();
+        assert_eq!(info.stride, 24);
+        assert_eq!(info.member("uv").unwrap().offset, 12);
+        assert_eq!(iter.len(), 72);
+
+        let positions: Vec<_> = (0..3).map(position).collect();
+        let uvs: Vec<_> = (0..3).map(uv).collect();
+        let (expected, expected_info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .with_stride(24)
+            .build()
+            .unwrap();
+        assert_eq!(info, expected_info);
+        assert_eq!(iter.collect::<Vec<_>>(), expected.collect_vec());
+
+        assert_eq!(
+            GeneratedVertexBuilder::new(3).build().unwrap_err(),
+            RuntimeVertexBuildError::NoAttributes,
+        );
+    }
+
+    #[test]
+    fn generated_vertices_fast_paths() {
+        let position = |index: usize| [index as f32, 0.0, 1.0];
+        let uv = |index: usize| [0.5f32, index as f32];
+        let generated = || {
+            GeneratedVertexBuilder::new(5)
+                .add(ATTRIBUTE_POSITION, position)
+                .add(ATTRIBUTE_UV, uv)
+                .with_stride(24)
+                .build()
+                .unwrap()
+                .0
+        };
+
+        let positions: Vec<_> = (0..5).map(position).collect();
+        let uvs: Vec<_> = (0..5).map(uv).collect();
+        let expected = || {
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add(ATTRIBUTE_UV, &uvs)
+                .with_stride(24)
+                .build()
+                .unwrap()
+                .0
+        };
+        let expected_data = expected().collect_vec();
+
+        assert_eq!(generated().collect_vec(), expected_data);
+
+        // The padding is zeroed even if the destination isn't.
+        let mut data = vec![0xff; expected_data.len()];
+        assert_eq!(
+            generated().write_to_slice(&mut data),
+            Ok(expected_data.len()),
+        );
+        assert_eq!(data, expected_data);
+        assert_eq!(
+            generated().write_to_slice(&mut data[1..]),
+            Err(RuntimeVertexWriteError::DestinationTooSmall {
+                required: expected_data.len(),
+                provided: expected_data.len() - 1,
+            }),
+        );
+
+        // Read in chunks that don't line up with the members or vertices.
+        let mut iter = generated();
+        let mut data = Vec::new();
+        let mut chunk = [0; 7];
+
+        loop {
+            let len = iter.read(&mut chunk).unwrap();
+
+            if len == 0 {
+                break;
+            }
+
+            data.extend_from_slice(&chunk[..len]);
+        }
+
+        assert_eq!(data, expected_data);
+
+        for n in [0, 5, 23, 24, 50, 119, 120, 200] {
+            let mut iter = generated();
+            let mut expected_iter = expected();
+            assert_eq!(iter.nth(n), expected_iter.nth(n));
+            assert_eq!(iter.len(), expected_iter.len());
+            assert_eq!(iter.collect_vec(), expected_iter.collect_vec());
+        }
+    }
+}
@@ -0,0 +1,692 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    builder::PadMode,
+    error::{RuntimeVertexBufferError, RuntimeVertexWriteError},
+    VertexBufferInfo,
+};
+use crate::{
+    buffer::{BufferUsage, CpuAccessibleBuffer},
+    memory::allocator::MemoryAllocator,
+    DeviceSize,
+};
+use std::{
+    borrow::Cow,
+    fmt::Debug,
+    io::{self, Read},
+    iter,
+    ops::Range,
+    sync::Arc,
+};
+
+/// An iterator over the bytes of interleaved vertex data, produced by
+/// [`RuntimeVertexBuilder`](super::RuntimeVertexBuilder).
+///
+/// Cloning the iterator is cheap when the data is borrowed, and the clone continues from the
+/// same position as the original. This allows emitting the same data more than once.
+#[derive(Clone, Debug)]
+pub struct RuntimeVertexIter<'d> {
+    // The data of each member, and the byte ranges of the members within a vertex, sorted by
+    // offset.
+    pub(super) slices: Vec<(Cow<'d, [u8]>, usize)>,
+    pub(super) member_ranges: Vec<Range<usize>>,
+    pub(super) member_names: Vec<String>,
+    // Whether each member was added with `add_constant` or `add_repeating`, so that its data is
+    // repeated from the start once the end is reached.
+    pub(super) repeating_members: Vec<bool>,
+    pub(super) stride: usize,
+    pub(super) data_index: usize,
+    pub(super) data_length: usize,
+    // How to fill in the elements that are past the end of a slice.
+    pub(super) pad: PadMode,
+    // The layout that was returned along with the iterator, for `into_parts`.
+    pub(super) info: VertexBufferInfo,
+}
+
+/// A [`RuntimeVertexIter`] that doesn't borrow any data, produced by an
+/// [`OwnedRuntimeVertexBuilder`](super::OwnedRuntimeVertexBuilder).
+pub type OwnedRuntimeVertexIter = RuntimeVertexIter<'static>;
+
+impl<'d> RuntimeVertexIter<'d> {
+    /// Rewinds the iterator to the start of the data, so that it can be consumed again.
+    ///
+    /// This is useful for retrying an upload that failed after some of the data was read.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.data_index = 0;
+    }
+
+    /// Returns the index of the vertex that the next byte belongs to, and the name of the
+    /// attribute that it belongs to.
+    ///
+    /// The name is `None` if the next byte is padding between or after the attributes, or if
+    /// there is no data left.
+    ///
+    /// This method takes precedence over [`Iterator::position`], which can still be called as
+    /// `Iterator::position(&mut iter, predicate)`.
+    pub fn position(&self) -> (usize, Option<&str>) {
+        let vertex_index = self.data_index / self.stride;
+        let vertex_offset = self.data_index % self.stride;
+
+        if self.len() == 0 {
+            return (vertex_index, None);
+        }
+
+        let name = self
+            .member_ranges
+            .iter()
+            .position(|range| range.contains(&vertex_offset))
+            .map(|member_index| self.member_names[member_index].as_str());
+
+        (vertex_index, name)
+    }
+
+    /// Returns the data of the attribute with the given name on its own, without the other
+    /// attributes or any padding, or `None` if there is no such attribute.
+    ///
+    /// The data is the same as what was added to the builder, with any byte order conversion
+    /// already applied. This is independent of how much of the interleaved data has been read.
+    pub fn attribute_bytes<'a>(&'a self, name: &str) -> Option<impl Iterator<Item = u8> + 'a> {
+        let member_index = self
+            .member_names
+            .iter()
+            .position(|member_name| member_name == name)?;
+
+        let (data, source_stride) = &self.slices[member_index];
+        let element_size = self.member_ranges[member_index].len();
+        let count = if self.repeating_members[member_index] {
+            self.data_length / self.stride
+        } else {
+            data.len() / source_stride
+        };
+
+        // The bytes between the elements of strided data are skipped.
+        Some(
+            data.chunks_exact(*source_stride)
+                .flat_map(move |element| &element[..element_size])
+                .copied()
+                .cycle()
+                .take(count * element_size),
+        )
+    }
+
+    /// Writes the remaining interleaved data directly into `dst`, and returns the number of bytes
+    /// that were written.
+    ///
+    /// This is the fastest way to write the data into a mapped buffer, as it copies the data of
+    /// each member of a vertex at once.
+    pub fn write_to_slice(mut self, dst: &mut [u8]) -> Result<usize, RuntimeVertexWriteError> {
+        let len = self.len();
+
+        if dst.len() < len {
+            return Err(RuntimeVertexWriteError::DestinationTooSmall {
+                required: len,
+                provided: dst.len(),
+            });
+        }
+
+        let mut written = 0;
+
+        while written < len {
+            written += self.copy_member(&mut dst[written..len]);
+        }
+
+        Ok(written)
+    }
+
+    /// Collects the remaining interleaved data into a `Vec`.
+    ///
+    /// Unlike `collect::<Vec<u8>>()`, this allocates the whole `Vec` up front and copies the
+    /// data of each member of a vertex at once.
+    pub fn collect_vec(mut self) -> Vec<u8> {
+        let len = self.len();
+        let mut data = vec![0; len];
+        let mut written = 0;
+
+        while written < len {
+            written += self.copy_member(&mut data[written..]);
+        }
+
+        data
+    }
+
+    /// Creates a host-visible buffer with the given `usage`, exactly the size of the remaining
+    /// interleaved data, and writes the data directly into it.
+    ///
+    /// This is equivalent to creating a `CpuAccessibleBuffer` with
+    /// [`from_iter`](CpuAccessibleBuffer::from_iter), but uses the fast path of
+    /// [`write_to_slice`](Self::write_to_slice). The memory is not host-cached, as the data is
+    /// only written once.
+    ///
+    /// Returns [`RuntimeVertexBufferError::NoData`] if there is no data left, as buffers can't be
+    /// empty.
+    pub fn into_buffer(
+        mut self,
+        allocator: &(impl MemoryAllocator + ?Sized),
+        usage: BufferUsage,
+    ) -> Result<Arc<CpuAccessibleBuffer<[u8]>>, RuntimeVertexBufferError> {
+        let len = self.len();
+
+        if len == 0 {
+            return Err(RuntimeVertexBufferError::NoData);
+        }
+
+        let buffer = unsafe {
+            CpuAccessibleBuffer::uninitialized_array(allocator, len as DeviceSize, usage, false)?
+        };
+
+        {
+            let mut mapping = buffer.write()?;
+            let mut written = 0;
+
+            while written < len {
+                written += self.copy_member(&mut mapping[written..]);
+            }
+        }
+
+        Ok(buffer)
+    }
+
+    /// Returns an iterator over the remaining interleaved data, one vertex at a time.
+    ///
+    /// Each item contains the `stride` bytes of a single vertex. If some bytes of the current
+    /// vertex were already consumed, the first item only contains the rest of that vertex.
+    pub fn vertices(mut self) -> impl Iterator<Item = Vec<u8>> + 'd {
+        iter::from_fn(move || {
+            let len = (self.stride - self.data_index % self.stride).min(self.len());
+
+            if len == 0 {
+                return None;
+            }
+
+            let mut vertex = vec![0; len];
+            let mut written = 0;
+
+            while written < len {
+                written += self.copy_member(&mut vertex[written..]);
+            }
+
+            Some(vertex)
+        })
+    }
+
+    /// Returns the data of each attribute on its own, along with the number of bytes from the
+    /// start of one of its elements to the next, and the layout of the interleaved data.
+    ///
+    /// The data is in the same order as the members of the layout. This allows uploading the
+    /// attributes as separate buffers instead of interleaving them on the CPU, regardless of how
+    /// much of the interleaved data has been read.
+    ///
+    /// The data is the same as what was added to the builder, with any byte order conversion
+    /// already applied. It is borrowed unless the builder had to convert it. Unlike the
+    /// interleaved data, it is not padded or trimmed to the number of vertices, and attributes
+    /// added with [`add_constant`] or [`add_repeating`] only contain the elements that are
+    /// repeated. The data of attributes added with [`add_strided`] keeps the bytes between the
+    /// elements, so their stride is the one they were added with rather than the size of an
+    /// element.
+    ///
+    /// [`add_strided`]: super::RuntimeVertexBuilder::add_strided
+    /// [`add_constant`]: super::RuntimeVertexBuilder::add_constant
+    /// [`add_repeating`]: super::RuntimeVertexBuilder::add_repeating
+    pub fn into_parts(self) -> (Vec<(Cow<'d, [u8]>, usize)>, VertexBufferInfo) {
+        // The slices are sorted by offset, which is a stable sort of the members of the layout.
+        let mut order: Vec<_> = (0..self.info.members.len()).collect();
+        order.sort_by_key(|&index| self.info.members[index].1.offset);
+
+        let mut parts: Vec<_> = order.into_iter().zip(self.slices).collect();
+        parts.sort_by_key(|&(index, _)| index);
+
+        (
+            parts.into_iter().map(|(_, slice)| slice).collect(),
+            self.info,
+        )
+    }
+
+    // Copies the bytes of the current member, starting at the current position, into `dst`.
+    // Returns the number of bytes that were copied, which is at most the number of bytes that are
+    // left in the member.
+    fn copy_member(&mut self, dst: &mut [u8]) -> usize {
+        let vertex_index = self.data_index / self.stride;
+        let vertex_offset = self.data_index % self.stride;
+
+        // The first member that ends after the current position. This doesn't depend on where
+        // the previous call left off, so any position within a vertex is handled correctly.
+        let member_index = self
+            .member_ranges
+            .partition_point(|range| range.end <= vertex_offset);
+
+        // Fill any padding before the next member, or at the end of the vertex, with zeroes.
+        let padding_end = self
+            .member_ranges
+            .get(member_index)
+            .map_or(self.stride, |range| range.start);
+
+        if vertex_offset < padding_end {
+            let len = (padding_end - vertex_offset).min(dst.len());
+            dst[..len].fill(0);
+            self.data_index += len;
+
+            return len;
+        }
+
+        let (data, source_stride) = &self.slices[member_index];
+        let source_stride = *source_stride;
+        let member_range = &self.member_ranges[member_index];
+        let member_offset = vertex_offset - member_range.start;
+        let len = (member_range.end - vertex_offset).min(dst.len());
+        let dst = &mut dst[..len];
+
+        let element_offset = if self.repeating_members[member_index] {
+            vertex_index % (data.len() / source_stride) * source_stride
+        } else {
+            vertex_index * source_stride
+        };
+
+        let start = if element_offset < data.len() {
+            Some(element_offset + member_offset)
+        } else {
+            match self.pad {
+                PadMode::Zero => None,
+                PadMode::Repeat => Some(data.len().saturating_sub(source_stride) + member_offset),
+            }
+        };
+
+        match start {
+            Some(start) => {
+                // The builder should never produce data that is too short, but a broken
+                // invariant is much easier to track down with the member and vertex at hand.
+                debug_assert!(
+                    start + len <= data.len(),
+                    "vertex {} of attribute `{}` reads bytes {}..{} of its data, which is {} bytes",
+                    vertex_index,
+                    self.member_names[member_index],
+                    start,
+                    start + len,
+                    data.len(),
+                );
+                dst.copy_from_slice(&data[start..start + len]);
+            }
+            None => dst.fill(0),
+        }
+
+        self.data_index += len;
+
+        len
+    }
+}
+
+impl<'d> Iterator for RuntimeVertexIter<'d> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        let mut byte = [0];
+
+        match self.read(&mut byte) {
+            Ok(1) => Some(byte[0]),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len();
+
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<u8> {
+        // The member to copy from is found from the position, so skipping is just a jump.
+        self.data_index += n.min(self.len());
+        self.next()
+    }
+}
+
+impl<'d> Read for RuntimeVertexIter<'d> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.len());
+        let mut written = 0;
+
+        while written < len {
+            written += self.copy_member(&mut buf[written..len]);
+        }
+
+        Ok(written)
+    }
+}
+
+impl<'d> ExactSizeIterator for RuntimeVertexIter<'d> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.data_length - self.data_index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::{BufferAccess, BufferUsage},
+        memory::allocator::StandardMemoryAllocator,
+        pipeline::graphics::vertex_input::{
+            runtime::tests::{ATTRIBUTE_POSITION, ATTRIBUTE_UV},
+            RuntimeVertexBufferError, RuntimeVertexBuilder, RuntimeVertexWriteError,
+        },
+    };
+    use std::{borrow::Cow, io::Read};
+
+    #[test]
+    fn vertices() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (mut iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+        assert_eq!(iter.next(), Some(bytemuck::bytes_of(&positions[0][0])[0]));
+
+        let vertices: Vec<Vec<u8>> = iter.vertices().collect();
+        assert_eq!(vertices.len(), 2);
+        assert_eq!(vertices[0].len(), 19);
+        assert_eq!(&vertices[0][11..], bytemuck::bytes_of(&uvs[0]));
+        assert_eq!(&vertices[1][..12], bytemuck::bytes_of(&positions[1]));
+        assert_eq!(&vertices[1][12..], bytemuck::bytes_of(&uvs[1]));
+    }
+
+    #[test]
+    fn read() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+        let uvs = [[10.0f32, 11.0], [12.0, 13.0], [14.0, 15.0]];
+
+        let builder = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs);
+        let expected: Vec<u8> = builder.clone().build().unwrap().0.collect();
+
+        // Read in chunks that don't line up with the members or vertices.
+        let (mut iter, _) = builder.build().unwrap();
+        let mut data = Vec::new();
+        let mut chunk = [0; 7];
+
+        loop {
+            let len = iter.read(&mut chunk).unwrap();
+
+            if len == 0 {
+                break;
+            }
+
+            data.extend_from_slice(&chunk[..len]);
+        }
+
+        assert_eq!(data, expected);
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn write_to_slice() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let builder = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs);
+        let expected: Vec<u8> = builder.clone().build().unwrap().0.collect();
+
+        let mut data = [0; 40];
+        assert_eq!(
+            builder
+                .clone()
+                .build()
+                .unwrap()
+                .0
+                .write_to_slice(&mut data[..39]),
+            Err(RuntimeVertexWriteError::DestinationTooSmall {
+                required: 40,
+                provided: 39,
+            }),
+        );
+        assert_eq!(builder.build().unwrap().0.write_to_slice(&mut data), Ok(40),);
+        assert_eq!(data[..], expected[..]);
+    }
+
+    #[test]
+    fn reset() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (mut iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+
+        let partial: Vec<u8> = Iterator::take(&mut iter, 22).collect();
+        assert_eq!(partial.len(), 22);
+        assert_eq!(iter.len(), 18);
+
+        iter.reset();
+        assert_eq!(iter.len(), 40);
+
+        let data = iter.collect_vec();
+        assert_eq!(&data[..22], partial.as_slice());
+        assert_eq!(
+            bytemuck::cast_slice::<_, f32>(&data),
+            &[1.0, 2.0, 3.0, 7.0, 8.0, 4.0, 5.0, 6.0, 9.0, 10.0],
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "vertex 1 of attribute `uv` reads bytes 8..16 of its data, which is 12 bytes"
+    )]
+    fn out_of_bounds_member_data() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (mut iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+
+        // Break the invariant that every member provides data for every vertex.
+        iter.slices[1].0 = Cow::Owned(vec![0; 12]);
+        iter.collect_vec();
+    }
+
+    #[test]
+    fn position() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (mut iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .with_stride(24)
+            .build()
+            .unwrap();
+        assert_eq!(iter.position(), (0, Some("position")));
+
+        let _ = Iterator::take(&mut iter, 12).count();
+        assert_eq!(iter.position(), (0, Some("uv")));
+
+        let _ = Iterator::take(&mut iter, 8).count();
+        assert_eq!(iter.position(), (0, None));
+
+        let _ = Iterator::take(&mut iter, 16).count();
+        assert_eq!(iter.position(), (1, Some("uv")));
+
+        let _ = Iterator::take(&mut iter, 12).count();
+        assert_eq!(iter.position(), (2, None));
+    }
+
+    #[test]
+    fn clone() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (mut iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+
+        let first = iter.clone().collect_vec();
+        let second = iter.clone().collect_vec();
+        assert_eq!(first, second);
+        assert_eq!(first, iter.clone().collect_vec());
+
+        // A clone picks up where the original left off.
+        let _ = Iterator::take(&mut iter, 20).count();
+        let rest = iter.clone().collect_vec();
+        assert_eq!(rest[..], first[20..]);
+        assert_eq!(iter.len(), 20);
+    }
+
+    #[test]
+    fn into_parts() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (mut iter, info) = RuntimeVertexBuilder::new()
+            .add_at(ATTRIBUTE_POSITION, &positions, 8)
+            .add_at(ATTRIBUTE_UV, &uvs, 0)
+            .build()
+            .unwrap();
+        let _ = Iterator::take(&mut iter, 4).count();
+
+        let (parts, parts_info) = iter.into_parts();
+        assert_eq!(parts_info, info);
+        assert_eq!(parts.len(), 2);
+        assert!(matches!(parts[0].0, Cow::Borrowed(_)));
+        assert_eq!(parts[0].0, bytemuck::cast_slice::<_, u8>(&positions));
+        assert_eq!(parts[0].1, 12);
+        assert_eq!(parts[1].0, bytemuck::cast_slice::<_, u8>(&uvs));
+        assert_eq!(parts[1].1, 8);
+    }
+
+    #[test]
+    fn into_buffer() {
+        let (device, _queue) = gfx_dev_and_queue!();
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+        let (iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+        let expected = iter.clone().collect_vec();
+
+        let buffer = iter
+            .into_buffer(&memory_allocator, BufferUsage::VERTEX_BUFFER)
+            .unwrap();
+        assert_eq!(buffer.size(), 40);
+        assert_eq!(&*buffer.read().unwrap(), &expected[..]);
+
+        let (mut iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .build()
+            .unwrap();
+        iter.nth(23);
+        assert!(matches!(
+            iter.into_buffer(&memory_allocator, BufferUsage::VERTEX_BUFFER),
+            Err(RuntimeVertexBufferError::NoData),
+        ));
+    }
+
+    #[test]
+    fn size_hint() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+
+        let (mut iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .build()
+            .unwrap();
+        assert_eq!(iter.size_hint(), (24, Some(24)));
+
+        iter.nth(9);
+        assert_eq!(iter.size_hint(), (14, Some(14)));
+        assert_eq!(iter.len(), 14);
+
+        iter.nth(100);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn attribute_bytes() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[5.0f32, 6.0], [7.0, 8.0]];
+
+        let (mut iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+        iter.next();
+
+        assert_eq!(
+            iter.attribute_bytes("uv").unwrap().collect::<Vec<_>>(),
+            bytemuck::cast_slice::<_, u8>(&uvs),
+        );
+        assert_eq!(
+            iter.attribute_bytes("position")
+                .unwrap()
+                .collect::<Vec<_>>(),
+            bytemuck::cast_slice::<_, u8>(&positions),
+        );
+        assert!(iter.attribute_bytes("normal").is_none());
+    }
+
+    #[test]
+    fn nth() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let builder = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs);
+        let data = builder.clone().build().unwrap().0.collect_vec();
+        let (mut iter, _) = builder.build().unwrap();
+
+        assert_eq!(iter.nth(12), Some(data[12]));
+        assert_eq!(iter.len(), 27);
+        assert_eq!(iter.nth(1), Some(data[14]));
+        assert_eq!(
+            Iterator::skip(&mut iter, 15).collect::<Vec<_>>(),
+            &data[30..]
+        );
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.nth(5), None);
+    }
+
+    #[test]
+    fn collect_vec() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let build = || {
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add_aligned(ATTRIBUTE_UV, &uvs)
+                .build()
+                .unwrap()
+                .0
+        };
+
+        let data = build().collect_vec();
+        assert_eq!(data.capacity(), 40);
+        assert_eq!(data, build().collect::<Vec<u8>>());
+    }
+}
@@ -0,0 +1,1016 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Interleaving of vertex data whose layout is only known at runtime.
+//!
+//! The [`Vertex`](super::Vertex) trait requires the layout of a vertex to be known at compile
+//! time. When the attributes of a mesh are only known at runtime, for example because they are
+//! read from a file, [`RuntimeVertexBuilder`] can be used instead. Each attribute is added as a
+//! separate slice, and the builder produces an iterator over the interleaved bytes of all
+//! attributes together with a [`VertexBufferInfo`] describing the resulting layout.
+//!
+//! # Examples
+//!
+//! ```
+//! use vulkano::{
+//!     format::Format,
+//!     pipeline::graphics::vertex_input::{RuntimeVertexBuilder, VertexAttribute},
+//! };
+//!
+//! const POSITION: VertexAttribute = VertexAttribute::new("position", Format::R32G32_SFLOAT);
+//! const COLOR: VertexAttribute = VertexAttribute::new("color", Format::R8G8B8A8_UNORM);
+//!
+//! let positions = [[0.0f32, 0.0], [1.0, 0.0], [0.0, 1.0]];
+//! let colors = [[255u8, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]];
+//!
+//! let (iter, info) = RuntimeVertexBuilder::new()
+//!     .add(POSITION, &positions)
+//!     .add(COLOR, &colors)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(info.stride, 12);
+//! assert_eq!(iter.len(), 3 * 12);
+//! ```
+
+pub use self::{
+    builder::{
+        Endianness, IntegerAlignment, OwnedRuntimeVertexBuilder, PadMode, RuntimeVertexBuilder,
+    },
+    error::{
+        DeinterleaveError, RuntimeVertexBufferError, RuntimeVertexBuildError,
+        RuntimeVertexWriteError,
+    },
+    generated::{GeneratedVertexBuilder, GeneratedVertexIter},
+    iter::{OwnedRuntimeVertexIter, RuntimeVertexIter},
+};
+use super::{
+    definition::attributes_by_name, IncompatibleVertexDefinitionError, VertexDefinition,
+    VertexInputBindingDescription, VertexInputRate, VertexInputState, VertexMemberInfo,
+};
+use crate::{
+    command_buffer::DrawIndirectCommand, format::Format, shader::ShaderInterface, DeviceSize,
+};
+use bytemuck::Pod;
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Write as FmtWrite},
+    iter::repeat,
+    ops::Range,
+};
+
+mod builder;
+mod error;
+mod generated;
+mod iter;
+
+/// The name and format of a vertex attribute, as used by [`RuntimeVertexBuilder`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VertexAttribute {
+    /// The name of the attribute, matched against the names of the vertex shader inputs.
+    pub name: Cow<'static, str>,
+
+    /// The format of a single element of the attribute.
+    pub format: Format,
+
+    /// The number of consecutive array elements or matrix columns using `format`.
+    ///
+    /// If `None`, the number is derived from the size of the data of the attribute.
+    pub num_elements: Option<u32>,
+
+    /// The offset of the attribute in bytes from the start of a vertex.
+    ///
+    /// If `None`, the attribute is placed after the previously added attribute.
+    pub offset: Option<usize>,
+}
+
+impl VertexAttribute {
+    /// Creates a new `VertexAttribute`.
+    #[inline]
+    pub const fn new(name: &'static str, format: Format) -> Self {
+        VertexAttribute {
+            name: Cow::Borrowed(name),
+            format,
+            num_elements: None,
+            offset: None,
+        }
+    }
+
+    /// Creates a new `VertexAttribute` for an array of `num_elements` elements, each of which
+    /// occupies a separate location.
+    #[inline]
+    pub const fn new_array(name: &'static str, format: Format, num_elements: u32) -> Self {
+        VertexAttribute {
+            name: Cow::Borrowed(name),
+            format,
+            num_elements: Some(num_elements),
+            offset: None,
+        }
+    }
+
+    /// Returns the attribute with its offset set to `offset`, for vertex layouts with members at
+    /// known offsets.
+    #[inline]
+    pub const fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Returns the format of a single element of the attribute.
+    #[inline]
+    pub const fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns whether `self` and `other` have the same format and number of elements, ignoring
+    /// their names and offsets.
+    ///
+    /// Unlike `==`, this only compares the representation of the data of the attributes.
+    #[inline]
+    pub fn same_format(&self, other: &VertexAttribute) -> bool {
+        self.format == other.format && self.num_elements == other.num_elements
+    }
+}
+
+/// Returns whether `attributes` can be added to a [`RuntimeVertexBuilder`] together: no two
+/// attributes have the same name, and every format can be used for vertex attributes.
+///
+/// This is a `const fn`, so tables of attributes can be checked at compile time:
+/// ```
+/// use vulkano::{
+///     format::Format,
+///     pipeline::graphics::vertex_input::{validate_attributes, VertexAttribute},
+/// };
+///
+/// const ATTRIBUTES: &[VertexAttribute] = &[
+///     VertexAttribute::new("position", Format::R32G32B32_SFLOAT),
+///     VertexAttribute::new("uv", Format::R32G32_SFLOAT),
+/// ];
+/// const _: () = assert!(validate_attributes(ATTRIBUTES));
+/// ```
+pub const fn validate_attributes(attributes: &[VertexAttribute]) -> bool {
+    let mut index = 0;
+
+    while index < attributes.len() {
+        if !attributes[index].format.is_vertex_attribute_format() {
+            return false;
+        }
+
+        let mut other = 0;
+
+        while other < index {
+            if bytes_eq(
+                name_bytes(&attributes[index]),
+                name_bytes(&attributes[other]),
+            ) {
+                return false;
+            }
+
+            other += 1;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+// `Deref` and `==` can't be used in const fns, so the name is taken out of its `Cow` by matching.
+const fn name_bytes(attribute: &VertexAttribute) -> &[u8] {
+    match &attribute.name {
+        Cow::Borrowed(name) => name.as_bytes(),
+        Cow::Owned(name) => name.as_bytes(),
+    }
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut index = 0;
+
+    while index < a.len() {
+        if a[index] != b[index] {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+/// Types that describe their fields as a list of [`VertexAttribute`]s.
+///
+/// The trait can be derived, with the format of each field specified using the `format`
+/// field-level attribute:
+/// ```
+/// use bytemuck::{Pod, Zeroable};
+/// use vulkano::pipeline::graphics::vertex_input::RuntimeVertex;
+///
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Debug, Default, Pod, Zeroable, RuntimeVertex)]
+/// struct MyVertex {
+///     #[format(R32G32B32_SFLOAT)]
+///     position: [f32; 3],
+///     // The `name` attribute can be used to specify the attribute name.
+///     // By default the field-name is used.
+///     #[name("in_uv")]
+///     #[format(R32G32_SFLOAT)]
+///     uv: [f32; 2],
+/// }
+///
+/// let attributes = MyVertex::attributes();
+/// assert_eq!(attributes[1].name, "in_uv");
+/// assert_eq!(attributes[1].offset, Some(12));
+/// ```
+pub trait RuntimeVertex: Pod {
+    /// Returns an attribute for each field of the type, with the offset and number of elements
+    /// of the field.
+    fn attributes() -> Vec<VertexAttribute>;
+}
+
+pub use vulkano_macros::RuntimeVertex;
+
+/// Describes the layout of a vertex buffer produced by [`RuntimeVertexBuilder`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VertexBufferInfo {
+    /// The members of each vertex, along with their names.
+    pub members: Vec<(String, VertexMemberInfo)>,
+
+    /// The number of bytes from the start of one vertex to the start of the next.
+    pub stride: u32,
+
+    /// How often the vertex input should advance to the next vertex.
+    pub input_rate: VertexInputRate,
+}
+
+impl VertexBufferInfo {
+    /// Returns the layout with its input rate set to `input_rate`.
+    ///
+    /// This allows the same layout to be used for per-vertex and per-instance data.
+    #[inline]
+    pub fn with_input_rate(mut self, input_rate: VertexInputRate) -> Self {
+        self.input_rate = input_rate;
+        self
+    }
+
+    /// Returns the member with the given name, if there is one.
+    #[inline]
+    pub fn member(&self, name: &str) -> Option<&VertexMemberInfo> {
+        self.members
+            .iter()
+            .find(|(member_name, _)| member_name == name)
+            .map(|(_, info)| info)
+    }
+
+    /// Returns the member that a shader input called `name` reads.
+    ///
+    /// This is the member with that name or, for the columns of a matrix added with
+    /// [`RuntimeVertexBuilder::add_matrix`], a single member with an element for each column.
+    fn shader_member(&self, name: &str) -> Option<VertexMemberInfo> {
+        if let Some(info) = self.member(name) {
+            return Some(info.clone());
+        }
+
+        let first = self.member(&format!("{}[0]", name))?;
+        let block_size = first.format.block_size()? as usize;
+        let is_column = |column: usize| {
+            matches!(
+                self.member(&format!("{}[{}]", name, column)),
+                Some(info) if info.format == first.format
+                    && info.num_elements == 1
+                    && info.offset == first.offset + column * block_size
+            )
+        };
+
+        (first.num_elements == 1).then(|| VertexMemberInfo {
+            num_elements: (1..).take_while(|&column| is_column(column)).count() as u32 + 1,
+            ..first.clone()
+        })
+    }
+
+    /// Returns the offset in bytes of the member with the given name from the start of a vertex,
+    /// if there is one.
+    #[inline]
+    pub fn offset_of(&self, name: &str) -> Option<DeviceSize> {
+        self.member(name).map(|info| info.offset as DeviceSize)
+    }
+
+    /// Returns the name of each member together with the bytes that it occupies within a vertex,
+    /// in the order of `members`.
+    ///
+    /// The range of a member starts at its offset, and covers all of its elements.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the format of a member has no block size.
+    pub fn member_ranges(&self) -> Vec<(String, Range<DeviceSize>)> {
+        self.members
+            .iter()
+            .map(|(name, info)| {
+                let offset = info.offset as DeviceSize;
+
+                (name.clone(), offset..offset + info.byte_size())
+            })
+            .collect()
+    }
+
+    /// Returns a human-readable description of the layout, for debugging.
+    ///
+    /// The first line contains the stride and input rate, followed by a line for each member in
+    /// the order of their offsets, in the form `name @ offset (format, num_elements)`. The
+    /// columns are aligned.
+    pub fn debug_layout(&self) -> String {
+        let mut members: Vec<_> = self.members.iter().collect();
+        members.sort_by_key(|(_, info)| info.offset);
+
+        let name_width = members
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        let offset_width = members
+            .iter()
+            .map(|(_, info)| info.offset.to_string().len())
+            .max()
+            .unwrap_or(0);
+
+        let mut layout = format!("stride {}, {:?}", self.stride, self.input_rate);
+
+        for (name, info) in members {
+            // Writing to a `String` can't fail.
+            write!(
+                layout,
+                "\n{:name_width$} @ {:>offset_width$} ({:?}, {})",
+                name,
+                info.offset,
+                info.format,
+                info.num_elements,
+                name_width = name_width,
+                offset_width = offset_width,
+            )
+            .unwrap();
+        }
+
+        layout
+    }
+}
+
+unsafe impl VertexDefinition for VertexBufferInfo {
+    #[inline]
+    fn definition(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<VertexInputState, IncompatibleVertexDefinitionError> {
+        self.definition_all(interface)
+            .map_err(|mut errors| errors.remove(0))
+    }
+
+    fn definition_all(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<VertexInputState, Vec<IncompatibleVertexDefinitionError>> {
+        let attributes = attributes_by_name(
+            interface,
+            |name| self.shader_member(name).map(|info| (info, 0)),
+            || self.members.iter().map(|(name, _)| name.clone()).collect(),
+        )?;
+
+        Ok(VertexInputState::new()
+            .binding(
+                0,
+                VertexInputBindingDescription {
+                    stride: self.stride,
+                    input_rate: self.input_rate,
+                },
+            )
+            .attributes(attributes))
+    }
+}
+
+/// Vertex data that is already interleaved, together with a description of its layout.
+///
+/// This is the counterpart of [`RuntimeVertexBuilder`] for data that doesn't need to be
+/// interleaved, for example because it was loaded from a file in that form. The data is only
+/// validated against the layout, and is never copied.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BorrowedVertexInput<'a> {
+    data: &'a [u8],
+    info: VertexBufferInfo,
+}
+
+impl<'a> BorrowedVertexInput<'a> {
+    /// Creates a new `BorrowedVertexInput` from interleaved data with the layout described by
+    /// `info`.
+    ///
+    /// Returns an error if the length of `data` is not a multiple of the stride, or if a member
+    /// extends past the end of a vertex.
+    pub fn new(data: &'a [u8], info: VertexBufferInfo) -> Result<Self, DeinterleaveError> {
+        validate_interleaved(data, &info)?;
+
+        Ok(BorrowedVertexInput { data, info })
+    }
+
+    /// Returns the interleaved data.
+    #[inline]
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the description of the layout of the data.
+    #[inline]
+    pub fn info(&self) -> &VertexBufferInfo {
+        &self.info
+    }
+
+    /// Returns the number of vertices in the data.
+    #[inline]
+    pub fn vertex_count(&self) -> usize {
+        match self.info.stride {
+            0 => 0,
+            stride => self.data.len() / stride as usize,
+        }
+    }
+
+    /// Returns a [`DrawIndirectCommand`] that draws all vertices of the data, with
+    /// `instance_count` instances and with `first_vertex` and `first_instance` set to 0.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the number of vertices doesn't fit in a `u32`.
+    #[inline]
+    pub fn draw_indirect_command(&self, instance_count: u32) -> DrawIndirectCommand {
+        DrawIndirectCommand {
+            vertex_count: self.vertex_count().try_into().unwrap(),
+            instance_count,
+            first_vertex: 0,
+            first_instance: 0,
+        }
+    }
+
+    /// Returns a `VertexInputState` that reads the data from binding 0, with the members
+    /// assigned to consecutive locations starting at 0.
+    ///
+    /// The locations are assigned like they are by
+    /// [`RuntimeVertexBuilder::into_vertex_input_state`].
+    #[inline]
+    pub fn vertex_input_state(&self) -> VertexInputState {
+        self.vertex_input_state_at(0)
+    }
+
+    /// Same as [`vertex_input_state`](Self::vertex_input_state), but the locations start at
+    /// `first_location` instead of 0.
+    pub fn vertex_input_state_at(&self, first_location: u32) -> VertexInputState {
+        builder::vertex_input_state(&self.info, 0, repeat(None), first_location)
+            .expect("consecutive locations can't collide")
+    }
+}
+
+unsafe impl<'a> VertexDefinition for BorrowedVertexInput<'a> {
+    #[inline]
+    fn definition(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<VertexInputState, IncompatibleVertexDefinitionError> {
+        self.info.definition(interface)
+    }
+
+    #[inline]
+    fn definition_all(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<VertexInputState, Vec<IncompatibleVertexDefinitionError>> {
+        self.info.definition_all(interface)
+    }
+}
+
+/// Interleaved vertex data produced by a [`RuntimeVertexBuilder`], together with a description
+/// of its layout.
+///
+/// Keeping the data and its layout together ensures that the data is always interpreted with the
+/// layout it was built with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterleavedVertexData {
+    bytes: Vec<u8>,
+    info: VertexBufferInfo,
+}
+
+impl InterleavedVertexData {
+    /// Builds the interleaved data of `builder` with [`RuntimeVertexBuilder::build`], and
+    /// collects it.
+    pub fn from_builder(
+        builder: RuntimeVertexBuilder<'_>,
+    ) -> Result<Self, RuntimeVertexBuildError> {
+        let (iter, info) = builder.build()?;
+
+        Ok(InterleavedVertexData {
+            bytes: iter.collect_vec(),
+            info,
+        })
+    }
+
+    /// Returns the interleaved data.
+    #[inline]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the description of the layout of the data.
+    #[inline]
+    pub fn info(&self) -> &VertexBufferInfo {
+        &self.info
+    }
+
+    /// Returns the number of vertices in the data.
+    #[inline]
+    pub fn vertex_count(&self) -> usize {
+        match self.info.stride {
+            0 => 0,
+            stride => self.bytes.len() / stride as usize,
+        }
+    }
+
+    /// Returns a [`DrawIndirectCommand`] that draws all vertices of the data, with
+    /// `instance_count` instances and with `first_vertex` and `first_instance` set to 0.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the number of vertices doesn't fit in a `u32`.
+    #[inline]
+    pub fn draw_indirect_command(&self, instance_count: u32) -> DrawIndirectCommand {
+        DrawIndirectCommand {
+            vertex_count: self.vertex_count().try_into().unwrap(),
+            instance_count,
+            first_vertex: 0,
+            first_instance: 0,
+        }
+    }
+
+    /// Returns a [`BorrowedVertexInput`] that refers to the data.
+    #[inline]
+    pub fn as_borrowed(&self) -> BorrowedVertexInput<'_> {
+        // The data was built with this layout, so it doesn't need to be validated again.
+        BorrowedVertexInput {
+            data: &self.bytes,
+            info: self.info.clone(),
+        }
+    }
+}
+
+unsafe impl VertexDefinition for InterleavedVertexData {
+    #[inline]
+    fn definition(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<VertexInputState, IncompatibleVertexDefinitionError> {
+        self.info.definition(interface)
+    }
+
+    #[inline]
+    fn definition_all(
+        &self,
+        interface: &ShaderInterface,
+    ) -> Result<VertexInputState, Vec<IncompatibleVertexDefinitionError>> {
+        self.info.definition_all(interface)
+    }
+}
+
+/// Splits interleaved vertex data back into the data of each member described by `info`.
+///
+/// This is the inverse of what [`RuntimeVertexBuilder`] does. Since the data of a member is not
+/// contiguous within interleaved data, it is copied into a tightly packed `Vec` for each member,
+/// in the order of `info.members`.
+pub fn deinterleave(
+    data: &[u8],
+    info: &VertexBufferInfo,
+) -> Result<Vec<(String, Vec<u8>)>, DeinterleaveError> {
+    validate_interleaved(data, info)?;
+
+    Ok(info
+        .members
+        .iter()
+        .map(|(name, member)| {
+            let range = member.offset..member.offset + member.byte_size() as usize;
+            let bytes = data
+                .chunks_exact(info.stride as usize)
+                .flat_map(|vertex| &vertex[range.clone()])
+                .copied()
+                .collect();
+
+            (name.clone(), bytes)
+        })
+        .collect())
+}
+
+// Checks that `data` consists of whole vertices of the layout described by `info`.
+fn validate_interleaved(data: &[u8], info: &VertexBufferInfo) -> Result<(), DeinterleaveError> {
+    let stride = info.stride as usize;
+    let remainder = if stride == 0 {
+        data.len()
+    } else {
+        data.len() % stride
+    };
+
+    if remainder != 0 {
+        return Err(DeinterleaveError::InvalidLength {
+            length: data.len(),
+            stride: info.stride,
+        });
+    }
+
+    for (name, member) in &info.members {
+        if member.offset + member.byte_size() as usize > stride {
+            return Err(DeinterleaveError::MemberOutOfBounds {
+                attribute: name.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        deinterleave, BorrowedVertexInput, DeinterleaveError, InterleavedVertexData,
+        RuntimeVertexBuildError, RuntimeVertexBuilder, RuntimeVertexIter, VertexAttribute,
+    };
+    use crate::{
+        command_buffer::DrawIndirectCommand,
+        format::Format,
+        pipeline::graphics::vertex_input::{
+            definition::tests::{float_type, shader_interface},
+            IncompatibleVertexDefinitionError, VertexDefinition, VertexInputRate, VertexInputState,
+        },
+    };
+    pub(super) const ATTRIBUTE_POSITION: VertexAttribute =
+        VertexAttribute::new("position", Format::R32G32B32_SFLOAT);
+    pub(super) const ATTRIBUTE_UV: VertexAttribute =
+        VertexAttribute::new("uv", Format::R32G32_SFLOAT);
+
+    // Asserts that `iter` produces `expected`, reporting where the first difference is.
+    #[track_caller]
+    pub(super) fn assert_interleaved_eq(mut iter: RuntimeVertexIter<'_>, expected: &[u8]) {
+        assert_eq!(
+            iter.len(),
+            expected.len(),
+            "the interleaved data has {} bytes, but {} were expected",
+            iter.len(),
+            expected.len(),
+        );
+
+        for (offset, &expected_byte) in expected.iter().enumerate() {
+            let (vertex_index, attribute) = iter.position();
+            let attribute = attribute.map_or("padding".to_owned(), |name| format!("`{}`", name));
+            let byte = iter.next().unwrap();
+            assert_eq!(
+                byte, expected_byte,
+                "byte {} of the interleaved data, in {} of vertex {}, is {:#04x}, but {:#04x} \
+                was expected",
+                offset, attribute, vertex_index, byte, expected_byte,
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "byte 13 of the interleaved data, in `uv` of vertex 0")]
+    fn assert_interleaved_eq_reports_position() {
+        let positions = [[1.0f32, 2.0, 3.0]];
+        let uvs = [[4.0f32, 5.0]];
+
+        let (iter, _) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+
+        let mut expected = iter.clone().collect_vec();
+        expected[13] ^= 1;
+        assert_interleaved_eq(iter, &expected);
+    }
+
+    #[test]
+    fn same_format() {
+        let normal = VertexAttribute::new("normal", Format::R32G32B32_SFLOAT);
+        assert_eq!(ATTRIBUTE_POSITION.format(), Format::R32G32B32_SFLOAT);
+        assert!(ATTRIBUTE_POSITION.same_format(&normal));
+        assert_ne!(ATTRIBUTE_POSITION, normal);
+        assert!(ATTRIBUTE_POSITION.same_format(&normal.with_offset(12)));
+
+        assert!(!ATTRIBUTE_POSITION.same_format(&ATTRIBUTE_UV));
+        assert!(!ATTRIBUTE_POSITION.same_format(&VertexAttribute::new_array(
+            "normal",
+            Format::R32G32B32_SFLOAT,
+            1,
+        )));
+    }
+
+    #[test]
+    fn validate_attributes() {
+        const ATTRIBUTES: &[VertexAttribute] = &[ATTRIBUTE_POSITION, ATTRIBUTE_UV];
+        const _: () = assert!(super::validate_attributes(ATTRIBUTES));
+
+        assert!(super::validate_attributes(&[]));
+        assert!(!super::validate_attributes(&[
+            ATTRIBUTE_POSITION,
+            ATTRIBUTE_UV,
+            VertexAttribute::new("position", Format::R32_SFLOAT),
+        ]));
+        assert!(!super::validate_attributes(&[VertexAttribute::new(
+            "depth",
+            Format::D16_UNORM,
+        )]));
+        assert!(!super::validate_attributes(&[
+            ATTRIBUTE_UV,
+            VertexAttribute {
+                name: "uv".to_owned().into(),
+                ..ATTRIBUTE_POSITION
+            },
+        ]));
+    }
+
+    #[test]
+    fn deinterleave_data() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (iter, info) = RuntimeVertexBuilder::new()
+            .add_at(ATTRIBUTE_POSITION, &positions, 8)
+            .add_at(ATTRIBUTE_UV, &uvs, 0)
+            .build()
+            .unwrap();
+        let data: Vec<u8> = iter.collect();
+
+        let members = deinterleave(&data, &info).unwrap();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].0, "position");
+        assert_eq!(members[0].1, bytemuck::cast_slice::<_, u8>(&positions));
+        assert_eq!(members[1].0, "uv");
+        assert_eq!(members[1].1, bytemuck::cast_slice::<_, u8>(&uvs));
+
+        assert_eq!(
+            deinterleave(&data[1..], &info).unwrap_err(),
+            DeinterleaveError::InvalidLength {
+                length: 39,
+                stride: 20,
+            },
+        );
+    }
+
+    #[test]
+    fn borrowed_vertex_input() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+
+        let (iter, state) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .into_vertex_input_state()
+            .unwrap();
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+        let data = iter.collect_vec();
+
+        let input = BorrowedVertexInput::new(&data, info.clone()).unwrap();
+        assert_eq!(input.data().as_ptr(), data.as_ptr());
+        assert_eq!(input.vertex_count(), 2);
+        let attributes = |state: VertexInputState| {
+            let mut attributes: Vec<_> = state
+                .attributes
+                .into_iter()
+                .map(|(location, attribute)| (location, attribute.offset, attribute.format))
+                .collect();
+            attributes.sort_by_key(|&(location, ..)| location);
+            attributes
+        };
+        assert_eq!(attributes(input.vertex_input_state()), attributes(state));
+        assert_eq!(input.vertex_input_state_at(2).attributes[&3].offset, 12);
+
+        let interface = shader_interface(&[("uv", 0, float_type(2, 1))]);
+        assert_eq!(
+            input.definition(&interface).unwrap().attributes[&0].offset,
+            12,
+        );
+
+        assert_eq!(
+            BorrowedVertexInput::new(&data[1..], info).unwrap_err(),
+            DeinterleaveError::InvalidLength {
+                length: 39,
+                stride: 20,
+            },
+        );
+    }
+
+    #[test]
+    fn interleaved_vertex_data() {
+        let positions = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let uvs = [[7.0f32, 8.0], [9.0, 10.0]];
+        let builder = || {
+            RuntimeVertexBuilder::new()
+                .add(ATTRIBUTE_POSITION, &positions)
+                .add(ATTRIBUTE_UV, &uvs)
+        };
+
+        let data = InterleavedVertexData::from_builder(builder()).unwrap();
+        let (iter, info) = builder().build().unwrap();
+        assert_eq!(data.bytes(), iter.collect_vec());
+        assert_eq!(data.info(), &info);
+        assert_eq!(data.vertex_count(), 2);
+
+        let borrowed = data.as_borrowed();
+        assert_eq!(borrowed.data(), data.bytes());
+        assert_eq!(borrowed.vertex_count(), 2);
+
+        let command = DrawIndirectCommand {
+            vertex_count: 2,
+            instance_count: 3,
+            first_vertex: 0,
+            first_instance: 0,
+        };
+        assert_eq!(data.draw_indirect_command(3), command);
+        assert_eq!(borrowed.draw_indirect_command(3), command);
+
+        assert_eq!(
+            InterleavedVertexData::from_builder(RuntimeVertexBuilder::new()).unwrap_err(),
+            RuntimeVertexBuildError::NoAttributes,
+        );
+    }
+
+    #[test]
+    fn derive_runtime_vertex() {
+        use super::RuntimeVertex;
+        use bytemuck::{Pod, Zeroable};
+
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, Default, Zeroable, Pod, RuntimeVertex)]
+        struct TestVertex {
+            #[format(R32G32B32A32_SFLOAT)]
+            matrix: [f32; 16],
+            #[name("in_scalar")]
+            #[format(R16_UINT)]
+            scalar: u16,
+            #[format(R8G8_UNORM)]
+            unorm: [u8; 2],
+        }
+
+        let attributes = TestVertex::attributes();
+        assert_eq!(attributes.len(), 3);
+        assert_eq!(attributes[0].name, "matrix");
+        assert_eq!(attributes[0].format, Format::R32G32B32A32_SFLOAT);
+        assert_eq!(attributes[0].num_elements, Some(4));
+        assert_eq!(attributes[0].offset, Some(0));
+        assert_eq!(attributes[1].name, "in_scalar");
+        assert_eq!(attributes[1].num_elements, Some(1));
+        assert_eq!(attributes[1].offset, Some(64));
+        assert_eq!(attributes[2].format, Format::R8G8_UNORM);
+        assert_eq!(attributes[2].offset, Some(66));
+    }
+
+    #[test]
+    fn debug_layout() {
+        let positions = [[0.0f32; 3]; 2];
+        let uvs = [[0.0f32; 2]; 2];
+        let weights = [[0.0f32; 8]; 2];
+
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_UV.with_offset(12), &uvs)
+            .add_at(ATTRIBUTE_POSITION, &positions, 0)
+            .add(
+                VertexAttribute::new_array("weights", Format::R32G32B32A32_SFLOAT, 2),
+                &weights,
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            info.debug_layout(),
+            "stride 52, Vertex\n\
+            position @  0 (R32G32B32_SFLOAT, 1)\n\
+            uv       @ 12 (R32G32_SFLOAT, 1)\n\
+            weights  @ 20 (R32G32B32A32_SFLOAT, 2)",
+        );
+    }
+
+    #[test]
+    fn member_ranges() {
+        let positions = [[0.0f32; 3]; 2];
+        let weights = [[0.0f32; 8]; 2];
+
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(
+                VertexAttribute::new_array("weights", Format::R32G32B32A32_SFLOAT, 2),
+                &weights,
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            info.member_ranges(),
+            [
+                ("position".to_owned(), 0..12),
+                ("weights".to_owned(), 12..44)
+            ],
+        );
+    }
+
+    #[test]
+    fn with_input_rate() {
+        let positions = [[1.0f32, 2.0, 3.0]];
+
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .build()
+            .unwrap();
+        let instance_info = info
+            .clone()
+            .with_input_rate(VertexInputRate::Instance { divisor: 1 });
+        assert_eq!(
+            instance_info.input_rate,
+            VertexInputRate::Instance { divisor: 1 },
+        );
+        assert_eq!(instance_info.members, info.members);
+        assert_eq!(instance_info.stride, info.stride);
+    }
+
+    #[test]
+    fn definition() {
+        let positions = [[0.0f32; 3]; 2];
+        let uvs = [[0.0f32; 2]; 2];
+
+        let (_, info) = RuntimeVertexBuilder::new()
+            .add(ATTRIBUTE_POSITION, &positions)
+            .add(ATTRIBUTE_UV, &uvs)
+            .build()
+            .unwrap();
+
+        let interface = shader_interface(&[
+            ("position", 0, float_type(3, 1)),
+            ("uv", 1, float_type(2, 1)),
+        ]);
+        assert_eq!(info.offset_of("uv"), Some(12));
+        assert_eq!(info.member("uv").unwrap().format, Format::R32G32_SFLOAT);
+        assert_eq!(info.member("normal"), None);
+
+        let state = info.definition(&interface).unwrap();
+        assert_eq!(state.bindings[&0].stride, 20);
+        assert_eq!(state.attributes[&0].format, Format::R32G32B32_SFLOAT);
+        assert_eq!(state.attributes[&1].offset, 12);
+
+        let interface = shader_interface(&[("position", 0, float_type(3, 4))]);
+        assert_eq!(
+            info.definition(&interface).unwrap_err(),
+            IncompatibleVertexDefinitionError::LocationOutOfRange {
+                attribute: "position".into(),
+                shader_locations: 4,
+                provided_locations: 1,
+            },
+        );
+
+        let interface = shader_interface(&[
+            ("position", 0, float_type(3, 4)),
+            ("normal", 4, float_type(3, 1)),
+            ("uv", 5, float_type(3, 1)),
+        ]);
+        let errors = info.definition_all(&interface).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            errors[1],
+            IncompatibleVertexDefinitionError::MissingAttribute {
+                attribute: "normal".into(),
+                location: 4,
+                shader_inputs: vec!["position".into(), "normal".into(), "uv".into()],
+                suggestion: None,
+            },
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            "attribute \"normal\" at location 4 required by the shader is missing from the vertex \
+            definition; shader declares: position, normal, uv",
+        );
+
+        let typo_interface = shader_interface(&[("postion", 0, float_type(3, 1))]);
+        assert_eq!(
+            info.definition(&typo_interface).unwrap_err().to_string(),
+            "attribute \"postion\" at location 0 required by the shader is missing from the \
+            vertex definition; shader declares: postion; did you mean \"position\"?",
+        );
+        assert!(matches!(
+            &errors[2],
+            IncompatibleVertexDefinitionError::FormatMismatch { attribute, location: 5, .. }
+                if attribute == "uv",
+        ));
+        assert_eq!(info.definition(&interface).unwrap_err(), errors[0]);
+    }
+}
@@ -7,7 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use crate::format::Format;
+use crate::{format::Format, DeviceSize};
 use bytemuck::Pod;
 pub use vulkano_macros::Vertex;
 
@@ -50,6 +50,7 @@ unsafe impl Vertex for () {
 
 /// Information about a member of a vertex struct.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexMemberInfo {
     /// Offset of the member in bytes from the start of the struct.
     pub offset: usize,
@@ -62,18 +63,28 @@ pub struct VertexMemberInfo {
 
 impl VertexMemberInfo {
     pub fn num_components(&self) -> u32 {
-        self.format
-            .components()
-            .iter()
-            .filter(|&bits| *bits > 0)
-            .count() as u32
+        self.format.component_count() as u32
+    }
+
+    /// Returns the number of bytes that the member occupies, which is the block size of `format`
+    /// multiplied by `num_elements`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `format` has no block size.
+    #[inline]
+    pub const fn byte_size(&self) -> DeviceSize {
+        match self.format.block_size() {
+            Some(block_size) => block_size * self.num_elements as u64,
+            None => panic!("no block size for format"),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::format::Format;
-    use crate::pipeline::graphics::vertex_input::Vertex;
+    use crate::pipeline::graphics::vertex_input::{Vertex, VertexMemberInfo};
 
     use bytemuck::{Pod, Zeroable};
 
@@ -93,6 +104,7 @@ mod tests {
         assert_eq!(c.format, Format::R32G32B32A32_SFLOAT);
         assert_eq!(b.num_elements, 4);
         assert_eq!(c.num_elements, 4);
+        assert_eq!(b.byte_size(), 64);
     }
 
     #[test]
@@ -108,4 +120,19 @@ mod tests {
         assert_eq!(unorm.format, Format::R8_UNORM);
         assert_eq!(unorm.num_elements, 1);
     }
+
+    #[test]
+    fn const_byte_size() {
+        const MATRIX: VertexMemberInfo = VertexMemberInfo {
+            offset: 0,
+            format: Format::R32G32B32A32_SFLOAT,
+            num_elements: 4,
+        };
+        const _: () = assert!(MATRIX.byte_size() == 64);
+        const _: () = assert!(matches!(
+            Format::A2B10G10R10_UNORM_PACK32.block_size(),
+            Some(4)
+        ));
+        const _: () = assert!(Format::D16_UNORM.block_size().is_none());
+    }
 }
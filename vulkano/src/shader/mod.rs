@@ -920,6 +920,12 @@ impl ShaderInterface {
         self.elements.as_ref()
     }
 
+    /// Returns an iterator over the elements of the interface.
+    #[inline]
+    pub fn entries(&self) -> impl ExactSizeIterator<Item = &ShaderInterfaceEntry> {
+        self.elements.iter()
+    }
+
     /// Checks whether the interface is potentially compatible with another one.
     ///
     /// Returns `Ok` if the two interfaces are compatible.
@@ -1003,9 +1009,18 @@ pub struct ShaderInterfaceEntryType {
 }
 
 impl ShaderInterfaceEntryType {
+    /// The number of locations occupied by a single array element or matrix column. A 64-bit
+    /// vector with more than two components takes up two locations.
+    pub(crate) fn locations_per_element(&self) -> u32 {
+        if self.is_64bit && self.num_components > 2 {
+            2
+        } else {
+            1
+        }
+    }
+
     pub(crate) fn num_locations(&self) -> u32 {
-        assert!(!self.is_64bit); // TODO: implement
-        self.num_elements
+        self.num_elements * self.locations_per_element()
     }
 }
 